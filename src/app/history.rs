@@ -0,0 +1,77 @@
+use std::io;
+
+// most recent entries kept in the history file
+const MAX_ENTRIES: usize = 100;
+
+/*
+ * Previously confirmed filters, oldest first, one per line in the history file. Without a file
+ * the history only lasts while the ui is open.
+ */
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<String>,
+    filename: Option<String>,
+    // entry shown while browsing with up and down
+    position: Option<usize>,
+}
+
+impl History {
+    // a missing or unreadable file starts an empty history
+    pub fn load(filename: Option<&str>) -> History {
+        let entries = filename
+            .and_then(|filename| std::fs::read_to_string(filename).ok())
+            .map(|content| content.lines().filter(|line| !line.is_empty()).map(String::from).collect())
+            .unwrap_or_default();
+        History {
+            entries,
+            filename: filename.map(String::from),
+            position: Option::None,
+        }
+    }
+
+    // moves the entry to the end when it was already there, then saves the file
+    pub fn push(&mut self, entry: &str) -> io::Result<()> {
+        self.position = Option::None;
+        self.entries.retain(|existing| existing != entry);
+        self.entries.push(entry.into());
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+        match &self.filename {
+            Option::Some(filename) => {
+                if let Option::Some(dir) = std::path::Path::new(filename).parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                let mut content = self.entries.join("\n");
+                content.push('\n');
+                std::fs::write(filename, content)
+            }
+            Option::None => Result::Ok(()),
+        }
+    }
+
+    pub fn previous(&mut self) -> Option<&str> {
+        let position = match self.position {
+            Option::Some(position) => position.checked_sub(1)?,
+            Option::None => self.entries.len().checked_sub(1)?,
+        };
+        self.position = Option::Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+
+    // after the newest entry the browsing stops and an empty text is returned
+    pub fn next(&mut self) -> Option<&str> {
+        let position = self.position? + 1;
+        if position < self.entries.len() {
+            self.position = Option::Some(position);
+            self.entries.get(position).map(String::as_str)
+        } else {
+            self.position = Option::None;
+            Option::Some("")
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.position = Option::None;
+    }
+}
@@ -1,12 +1,22 @@
+pub mod history;
+mod tests;
+mod transaction;
+pub mod ui;
+
 use crate::config::Config;
+use crate::model::common::ParsingError;
+use crate::model::Calendar;
 use crate::model::Model;
+use crate::model::Settings;
 use crate::model::TodoData;
 use crate::args::Command;
 use crate::Error;
+use std::io::Write;
 
 pub struct App {
     config: Config,
     model: crate::model::Model,
+    problems: Vec<ParsingError>,
 }
 
 impl App {
@@ -17,14 +27,32 @@ impl App {
                 done_filename: Option::Some(done_filename),
                 ..
             } => {
-                let todo_str = std::fs::read_to_string(&todo_filename)?;
-                let done_str = std::fs::read_to_string(&done_filename)?;
+                let todo_str = std::fs::read_to_string(todo_filename)?;
+                let done_str = std::fs::read_to_string(done_filename)?;
+                let calendar = match &config.holidays_filename {
+                    Option::Some(holidays_filename) => {
+                        Calendar::parse(&std::fs::read_to_string(holidays_filename)?)
+                            .map_err(|error| error.in_file(holidays_filename))?
+                    }
+                    Option::None => Calendar::default(),
+                };
+                let (todo_data, mut problems) = App::parse_data(todo_filename, &todo_str, config.lenient)?;
+                let (done_data, done_problems) = App::parse_data(done_filename, &done_str, config.lenient)?;
+                problems.extend(done_problems);
                 Result::Ok(App {
-                    config,
                     model: Model {
-                        todo_data: TodoData::parse(&todo_str).expect("error parsing todo file"),
-                        done_data: TodoData::parse(&done_str).expect("error parsing done file"),
+                        todo_data,
+                        done_data,
+                        settings: Settings {
+                            calendar,
+                            auto_uuid: config.auto_uuid,
+                            date_on_add: config.date_on_add,
+                            colors: config.colors.clone(),
+                            today: Option::None,
+                        },
                     },
+                    config,
+                    problems,
                 })
             },
             _ => {
@@ -33,20 +61,111 @@ impl App {
         }
     }
 
+    // in lenient mode lines that cannot be parsed are reported instead of failing
+    fn parse_data(filename: &str, data: &str, lenient: bool) -> Result<(TodoData, Vec<ParsingError>), ParsingError> {
+        if lenient {
+            let (todo_data, problems) = TodoData::parse_lenient(data);
+            let problems = problems.into_iter().map(|problem| problem.in_file(filename)).collect();
+            Result::Ok((todo_data, problems))
+        } else {
+            TodoData::parse(data)
+                .map(|todo_data| (todo_data, Vec::new()))
+                .map_err(|error| error.in_file(filename))
+        }
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    pub fn problems(&self) -> &[ParsingError] {
+        &self.problems
+    }
+
     pub fn execute(&mut self, command: Command)->Result<(), Error> {
-        self.model.execute(command).map_err(|e| Error {
-            message: e.to_owned(),
-        })
+        match command {
+            Command::Check => {
+                for problem in &self.problems {
+                    println!("{}", problem);
+                }
+                if self.problems.is_empty() {
+                    Result::Ok(())
+                } else {
+                    Result::Err(Error {
+                        message: format!("{} problem(s) found", self.problems.len()),
+                    })
+                }
+            }
+            Command::Edit(selector) => {
+                let index = self.model.select_one(&selector)?;
+                let editor = self
+                    .config
+                    .editor
+                    .clone()
+                    .or_else(|| std::env::var("EDITOR").ok())
+                    .unwrap_or_else(|| "vi".into());
+                let line = App::edit_line(&editor, &format!("{}", self.model.todo_data.entries[index]))?;
+                self.model.edit(index, &line)?;
+                Result::Ok(())
+            }
+            Command::AddPrompt => match ui::prompt(self, "add: ")? {
+                Option::Some(text) => self.execute(Command::Add(text)),
+                Option::None => Result::Ok(()),
+            },
+            Command::Ui => ui::run(self),
+            command => self.model.execute(command).map_err(|e| Error {
+                message: e.to_owned(),
+            }),
+        }
+    }
+
+    // lets the user change the line with the editor command through a temporary file
+    fn edit_line(editor: &str, line: &str) -> Result<String, Error> {
+        let path = std::env::temp_dir().join(format!("tcd-edit-{}.txt", std::process::id()));
+        // a new file only readable by the user, an existing file or symlink is never written through
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path).map_err(|error| Error {
+            message: format!("cannot create {}: {}", path.display(), error),
+        })?;
+        let written = writeln!(file, "{}", line);
+        drop(file);
+        if let Result::Err(error) = written {
+            let _ = std::fs::remove_file(&path);
+            return Result::Err(error.into());
+        }
+        let mut words = editor.split_whitespace();
+        let program = words.next().ok_or("the editor command is empty")?;
+        let status = std::process::Command::new(program).args(words).arg(&path).status();
+        let edited = std::fs::read_to_string(&path);
+        std::fs::remove_file(&path)?;
+        if !status?.success() {
+            return Result::Err("the editor exited with an error".into());
+        }
+        match edited?.lines().find(|line| !line.trim().is_empty()) {
+            Option::Some(line) => Result::Ok(line.into()),
+            Option::None => Result::Err("the edited task is empty".into()),
+        }
     }
 
     pub fn save_model(&mut self) -> Result<(), Error> {
-        let mut todo_data_str = String::new();
-        for entry in &self.model.todo_data.entries {
-            todo_data_str.push_str(&format!("{}\n", entry));
+        match &self.config {
+            Config {
+                todo_filename: Option::Some(todo_filename),
+                done_filename: Option::Some(done_filename),
+                ..
+            } => {
+                transaction::write_all(&[
+                    (todo_filename, format!("{}", self.model.todo_data)),
+                    (done_filename, format!("{}", self.model.done_data)),
+                ])?;
+                Result::Ok(())
+            }
+            _ => Result::Err(Error {
+                message: "cannot save model".into(),
+            }),
         }
-        let o = &self.config.todo_filename.as_ref();
-        let p : String = o.unwrap().clone();
-        std::fs::write(p, todo_data_str)?;
-        Result::Ok(())
     }
 }
\ No newline at end of file
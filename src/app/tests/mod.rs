@@ -0,0 +1,2 @@
+pub mod ui_tests;
+pub mod transaction_tests;
@@ -0,0 +1,63 @@
+/*
+ * Tests of writing several files at once
+ */
+#[allow(unused_imports)]
+use crate::app::transaction::write_all;
+#[allow(unused_imports)]
+use crate::model::tests::command_tests::create_files_for_testing;
+
+#[test]
+pub fn failed_second_rename_restores_the_first_file() {
+    let (todo_path, done_path) = create_files_for_testing("transaction", "call mom\n", "");
+    // the done target is a directory, a file cannot be renamed over it
+    std::fs::remove_file(&done_path).unwrap();
+    std::fs::create_dir(&done_path).unwrap();
+    std::fs::write(done_path.join("keep"), "").unwrap();
+    let result = write_all(&[
+        (&todo_path.to_string_lossy(), "call dad\n".into()),
+        (&done_path.to_string_lossy(), "x call mom\n".into()),
+    ]);
+    assert!(result.is_err());
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call mom\n");
+    assert!(!todo_path.with_extension("txt.bak").exists());
+    assert!(!todo_path.with_extension("txt.tmp").exists());
+    assert!(!done_path.with_extension("txt.tmp").exists());
+    assert!(done_path.join("keep").exists());
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn files_are_replaced_without_leftovers() {
+    let (todo_path, done_path) = create_files_for_testing("transaction-ok", "call mom\n", "");
+    write_all(&[
+        (&todo_path.to_string_lossy(), "call dad\n".into()),
+        (&done_path.to_string_lossy(), "x call mom\n".into()),
+    ])
+    .unwrap();
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call dad\n");
+    assert_eq!(std::fs::read_to_string(&done_path).unwrap(), "x call mom\n");
+    let names: Vec<String> = std::fs::read_dir(todo_path.parent().unwrap())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names.len(), 2);
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn existing_backup_and_temporary_files_are_kept() {
+    let (todo_path, done_path) = create_files_for_testing("transaction-keep", "call mom\n", "");
+    let (todo, done) = (todo_path.to_string_lossy(), done_path.to_string_lossy());
+    let files = [(&*todo, "call dad\n".to_string()), (&*done, "x call mom\n".to_string())];
+    for extension in &["txt.bak", "txt.tmp"] {
+        let leftover = done_path.with_extension(extension);
+        std::fs::write(&leftover, "mine\n").unwrap();
+        assert!(write_all(&files).is_err());
+        assert_eq!(std::fs::read_to_string(&leftover).unwrap(), "mine\n");
+        assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call mom\n");
+        assert!(!todo_path.with_extension("txt.bak").exists());
+        assert!(!todo_path.with_extension("txt.tmp").exists());
+        std::fs::remove_file(&leftover).unwrap();
+    }
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
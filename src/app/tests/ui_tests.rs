@@ -0,0 +1,162 @@
+/*
+ * Terminal ui key handling tests
+ */
+#[allow(unused_imports)]
+use crate::app::history::History;
+#[allow(unused_imports)]
+use crate::app::ui::{LineInput, Ui};
+#[allow(unused_imports)]
+use crate::app::App;
+#[allow(unused_imports)]
+use crate::config::Config;
+#[allow(unused_imports)]
+use crate::model::Status;
+#[allow(unused_imports)]
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+#[allow(unused_imports)]
+use crate::model::tests::command_tests::create_files_for_testing;
+#[allow(unused_imports)]
+use std::path::PathBuf;
+
+#[allow(dead_code)]
+fn create_app_for_testing(name: &str, todo: &str) -> (App, PathBuf) {
+    let (todo_path, done_path) = create_files_for_testing(&format!("ui-{}", name), todo, "");
+    let app = App::new(Config {
+        todo_filename: Option::Some(todo_path.to_string_lossy().into()),
+        done_filename: Option::Some(done_path.to_string_lossy().into()),
+        ..Config::new()
+    })
+    .unwrap();
+    (app, todo_path)
+}
+
+#[allow(dead_code)]
+fn press(ui: &mut Ui, app: &mut App, keys: &[KeyCode]) -> bool {
+    keys.iter()
+        .all(|code| ui.handle_key(app, KeyEvent::new(*code, KeyModifiers::NONE)))
+}
+
+#[allow(dead_code)]
+fn type_text(text: &str) -> Vec<KeyCode> {
+    text.chars().map(KeyCode::Char).collect()
+}
+
+#[test]
+pub fn toggling_done_saves_right_away() {
+    let (mut app, todo_path) = create_app_for_testing("toggle", "call mom\ncall dad\n");
+    let mut ui = Ui::default();
+    assert!(press(&mut ui, &mut app, &[KeyCode::Char('j'), KeyCode::Char(' ')]));
+    let todo = std::fs::read_to_string(&todo_path).unwrap();
+    assert!(todo.starts_with("call mom\nx "));
+    press(&mut ui, &mut app, &[KeyCode::Char(' ')]);
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call mom\ncall dad\n");
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn add_and_delete_through_prompts() {
+    let (mut app, todo_path) = create_app_for_testing("add", "call mom\n");
+    let mut ui = Ui::default();
+    let mut keys = vec![KeyCode::Char('a')];
+    keys.extend(type_text("call dad"));
+    keys.push(KeyCode::Enter);
+    press(&mut ui, &mut app, &keys);
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call mom\ncall dad\n");
+    press(&mut ui, &mut app, &[KeyCode::Char('d'), KeyCode::Char('n')]);
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call mom\ncall dad\n");
+    press(&mut ui, &mut app, &[KeyCode::Char('d'), KeyCode::Char('y')]);
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call dad\n");
+    assert!(!press(&mut ui, &mut app, &[KeyCode::Char('q')]));
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn rejected_task_stays_in_the_prompt() {
+    let (mut app, todo_path) = create_app_for_testing("rejected", "call mom\n");
+    let mut ui = Ui::default();
+    let mut keys = vec![KeyCode::Char('a')];
+    keys.extend(type_text("call dad due:someday"));
+    keys.push(KeyCode::Enter);
+    press(&mut ui, &mut app, &keys);
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call mom\n");
+    let mut keys = vec![KeyCode::Backspace; "someday".len()];
+    keys.extend(type_text("2024-05-01"));
+    keys.push(KeyCode::Enter);
+    press(&mut ui, &mut app, &keys);
+    assert_eq!(
+        std::fs::read_to_string(&todo_path).unwrap(),
+        "call mom\ncall dad due:2024-05-01\n"
+    );
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn tasks_past_the_selector_range_are_left_alone() {
+    let todo: String = (0..65537).map(|i| format!("task {}\n", i)).collect();
+    let (mut app, todo_path) = create_app_for_testing("range", &todo);
+    let mut ui = Ui::default();
+    press(&mut ui, &mut app, &[KeyCode::End, KeyCode::Char(' ')]);
+    assert!(app.model().todo_data.entries.iter().all(|entry| entry.status == Status::Open));
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), todo);
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn line_input_edits_at_the_cursor() {
+    let mut input = LineInput::new("call mom");
+    for code in &[KeyCode::Home, KeyCode::Delete, KeyCode::Char('C'), KeyCode::End, KeyCode::Backspace] {
+        input.handle_key(KeyEvent::new(*code, KeyModifiers::NONE));
+    }
+    assert_eq!(input.text(), "Call mo");
+    assert_eq!(input.position(), 7);
+}
+
+#[test]
+pub fn filter_bar_narrows_the_list_while_typing() {
+    let (mut app, todo_path) = create_app_for_testing("filter", "call mom +family\nwrite report +work\nwrap gifts +family\n");
+    let history_path = todo_path.with_file_name("history");
+    let mut ui = Ui::with_history(History::load(history_path.to_str()));
+    let mut keys = vec![KeyCode::Char('/')];
+    keys.extend(type_text("+family wrgf"));
+    keys.extend(vec![KeyCode::Enter, KeyCode::Char(' ')]);
+    press(&mut ui, &mut app, &keys);
+    let todo = std::fs::read_to_string(&todo_path).unwrap();
+    assert!(todo.starts_with("call mom +family\nwrite report +work\nx "));
+    assert_eq!(std::fs::read_to_string(&history_path).unwrap(), "+family wrgf\n");
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn filter_history_is_browsed_newest_first() {
+    let mut history = History::default();
+    history.push("+work").unwrap();
+    history.push("@phone").unwrap();
+    history.push("+work").unwrap();
+    assert_eq!(history.previous(), Option::Some("+work"));
+    assert_eq!(history.previous(), Option::Some("@phone"));
+    assert_eq!(history.previous(), Option::None);
+    assert_eq!(history.next(), Option::Some("+work"));
+    assert_eq!(history.next(), Option::Some(""));
+}
+
+#[test]
+pub fn tab_completes_the_word_before_the_cursor() {
+    let mut input = LineInput::new("call +fa");
+    input.complete(&["+family".to_string()]);
+    assert_eq!(input.text(), "call +family ");
+    let mut input = LineInput::new("pay due:t");
+    input.complete(&["due:today".to_string(), "due:tomorrow".to_string()]);
+    assert_eq!((input.text(), input.position()), ("pay due:to".to_string(), 10));
+}
+
+#[test]
+pub fn add_dialog_completes_projects() {
+    let (mut app, todo_path) = create_app_for_testing("complete", "call mom +family\n");
+    let mut ui = Ui::default();
+    let mut keys = vec![KeyCode::Char('a')];
+    keys.extend(type_text("call dad +fa"));
+    keys.extend(vec![KeyCode::Tab, KeyCode::Enter]);
+    press(&mut ui, &mut app, &keys);
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "call mom +family\ncall dad +family\n");
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,99 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+fn sibling(filename: &str, extension: &str) -> PathBuf {
+    PathBuf::from(format!("{}.{}", filename, extension))
+}
+
+/*
+ * Writes every (filename, content) pair or none of them. Contents are staged in
+ * temporary files first and every existing file gets a backup link, then each
+ * temporary file is renamed over its target so a file is always either old or
+ * new. A failure midway restores the previous files from their backups. Leftover
+ * temporary or backup files are never overwritten, the write fails instead.
+ */
+pub fn write_all(files: &[(&str, String)]) -> io::Result<()> {
+    let mut staged: Vec<PathBuf> = Vec::new();
+    for (filename, content) in files {
+        let tmp = sibling(filename, "tmp");
+        if let Result::Err(error) = create_new(&tmp, content) {
+            remove_all(&staged);
+            return Result::Err(error);
+        }
+        staged.push(tmp);
+    }
+
+    let mut backups: Vec<Option<PathBuf>> = Vec::new();
+    for (filename, _) in files {
+        match backup(filename) {
+            Result::Ok(backup) => backups.push(backup),
+            Result::Err(error) => {
+                remove_all(&staged);
+                remove_all(&backups.into_iter().flatten().collect::<Vec<PathBuf>>());
+                return Result::Err(error);
+            }
+        }
+    }
+
+    for (i, ((filename, _), tmp)) in files.iter().zip(staged.iter()).enumerate() {
+        if let Result::Err(error) = fs::rename(tmp, filename) {
+            rollback(&files[..i], &backups[..i]);
+            remove_all(&staged[i..]);
+            remove_all(&backups[i..].iter().flatten().cloned().collect::<Vec<PathBuf>>());
+            return Result::Err(error);
+        }
+    }
+
+    remove_all(&backups.into_iter().flatten().collect::<Vec<PathBuf>>());
+    Result::Ok(())
+}
+
+fn create_new(path: &PathBuf, content: &str) -> io::Result<()> {
+    let mut file = fs::OpenOptions::new().write(true).create_new(true).open(path).map_err(|error| {
+        io::Error::new(error.kind(), format!("cannot create {}: {}", path.display(), error))
+    })?;
+    let written = io::Write::write_all(&mut file, content.as_bytes()).and_then(|_| file.sync_all());
+    if written.is_err() {
+        let _ = fs::remove_file(path);
+    }
+    written
+}
+
+// a second name for the current file, or a copy where links are not supported
+fn backup(filename: &str) -> io::Result<Option<PathBuf>> {
+    if !PathBuf::from(filename).is_file() {
+        return Result::Ok(Option::None);
+    }
+    let backup = sibling(filename, "bak");
+    if backup.exists() {
+        return Result::Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists, move it away to save", backup.display()),
+        ));
+    }
+    if fs::hard_link(filename, &backup).is_err() {
+        fs::copy(filename, &backup)?;
+    }
+    Result::Ok(Option::Some(backup))
+}
+
+// moves back the files replaced so far, files that did not exist are removed again
+fn rollback(files: &[(&str, String)], backups: &[Option<PathBuf>]) {
+    for ((filename, _), backup) in files.iter().zip(backups.iter()).rev() {
+        match backup {
+            Option::Some(backup) => {
+                let _ = fs::rename(backup, filename);
+            }
+            Option::None => {
+                let _ = fs::remove_file(filename);
+            }
+        }
+    }
+}
+
+fn remove_all(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,461 @@
+use super::history::History;
+use super::App;
+use crate::args::Command;
+use crate::args::ListOptions;
+use crate::model::Filter;
+use crate::model::Renderer;
+use crate::model::Selector;
+use crate::model::Status;
+use crate::Error;
+use crossterm::cursor;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{execute, queue};
+use std::convert::TryFrom;
+use std::io::Write;
+
+const HELP: &str = "j/k move  space done/undo  a add  e edit  d delete  A archive done  / filter  q quit";
+
+/*
+ * Full screen task list. Every change is saved right away so the files stay current while the
+ * screen is kept open.
+ */
+#[derive(Default)]
+pub struct Ui {
+    // position in the visible rows
+    cursor: usize,
+    // first visible row when the list is longer than the screen
+    offset: usize,
+    mode: Mode,
+    message: String,
+    // list filter with text terms matched fuzzily, and the text it was parsed from
+    filter: Option<Filter>,
+    filter_text: String,
+    history: History,
+}
+
+#[derive(Default)]
+enum Mode {
+    #[default]
+    Browse,
+    ConfirmDelete(usize),
+    Input(Prompt, LineInput),
+}
+
+enum Prompt {
+    Add,
+    Edit(usize),
+    // filter text to go back to when the filter bar is cancelled
+    Filter(String),
+}
+
+impl Prompt {
+    fn label(&self) -> &'static str {
+        match self {
+            Prompt::Add => "add: ",
+            Prompt::Edit(_) => "edit: ",
+            Prompt::Filter(_) => "/",
+        }
+    }
+}
+
+// single line text field, the position is counted in chars
+#[derive(Debug, PartialEq, Default)]
+pub struct LineInput {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl LineInput {
+    pub fn new(text: &str) -> LineInput {
+        let chars: Vec<char> = text.chars().collect();
+        LineInput {
+            position: chars.len(),
+            chars,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    // the text between the last whitespace before the cursor and the cursor
+    pub fn word_before_cursor(&self) -> String {
+        self.chars[..self.position]
+            .iter()
+            .rev()
+            .take_while(|c| !c.is_whitespace())
+            .collect::<Vec<&char>>()
+            .into_iter()
+            .rev()
+            .collect()
+    }
+
+    /*
+     * Replaces the word before the cursor with the only candidate, followed by a space unless it
+     * expects a value, or extends it to the prefix shared by every candidate.
+     */
+    pub fn complete(&mut self, candidates: &[String]) {
+        let word_length = self.word_before_cursor().chars().count();
+        let replacement: String = match candidates {
+            [] => return,
+            [candidate] if candidate.ends_with(':') => candidate.clone(),
+            [candidate] => format!("{} ", candidate),
+            [first, rest @ ..] => {
+                let mut shared: Vec<char> = first.chars().collect();
+                for candidate in rest {
+                    let common = shared.iter().zip(candidate.chars()).take_while(|(a, b)| **a == *b).count();
+                    shared.truncate(common);
+                }
+                shared.into_iter().collect()
+            }
+        };
+        let start = self.position - word_length;
+        self.chars.splice(start..self.position, replacement.chars());
+        self.position = start + replacement.chars().count();
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => {
+                self.chars.insert(self.position, c);
+                self.position += 1;
+            }
+            KeyCode::Backspace if self.position > 0 => {
+                self.position -= 1;
+                self.chars.remove(self.position);
+            }
+            KeyCode::Delete if self.position < self.chars.len() => {
+                self.chars.remove(self.position);
+            }
+            KeyCode::Left => self.position = self.position.saturating_sub(1),
+            KeyCode::Right => self.position = (self.position + 1).min(self.chars.len()),
+            KeyCode::Home => self.position = 0,
+            KeyCode::End => self.position = self.chars.len(),
+            _ => (),
+        }
+    }
+}
+
+// puts the terminal back when dropped, also when the ui returns early with an error or panics
+struct Screen;
+
+impl Screen {
+    fn enter(out: &mut impl Write) -> Result<Screen, Error> {
+        terminal::enable_raw_mode()?;
+        let screen = Screen;
+        execute!(out, terminal::EnterAlternateScreen, terminal::DisableLineWrap, cursor::Hide)?;
+        Result::Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(
+            std::io::stdout(),
+            cursor::Show,
+            terminal::EnableLineWrap,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+pub fn run(app: &mut App) -> Result<(), Error> {
+    let mut out = std::io::stdout();
+    let _screen = Screen::enter(&mut out)?;
+    let history = History::load(app.config.history_filename.as_deref());
+    Ui::with_history(history).event_loop(app, &mut out)
+}
+
+/*
+ * Reads a line below the command with completion on tab, None when cancelled with escape. When
+ * several candidates match they are listed on their own line before the prompt is written again.
+ */
+pub fn prompt(app: &App, label: &str) -> Result<Option<String>, Error> {
+    let mut out = std::io::stdout();
+    let mut input = LineInput::default();
+    terminal::enable_raw_mode()?;
+    let result = loop {
+        // errors leave the loop so raw mode is always turned off again
+        let column = u16::try_from(label.chars().count() + input.position()).unwrap_or(u16::MAX);
+        if let Result::Err(error) = execute!(
+            out,
+            cursor::MoveToColumn(0),
+            terminal::Clear(ClearType::CurrentLine),
+            Print(label),
+            Print(input.text()),
+            cursor::MoveToColumn(column)
+        ) {
+            break Result::Err(error);
+        }
+        let key = match event::read() {
+            Result::Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key,
+            Result::Ok(_) => continue,
+            Result::Err(error) => break Result::Err(error),
+        };
+        match key.code {
+            KeyCode::Enter => break Result::Ok(Option::Some(input.text())),
+            KeyCode::Esc => break Result::Ok(Option::None),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break Result::Ok(Option::None),
+            KeyCode::Tab => {
+                let candidates = app.model.completions(&input.word_before_cursor());
+                input.complete(&candidates);
+                if candidates.len() > 1 {
+                    if let Result::Err(error) =
+                        execute!(out, Print("\r\n"), Print(candidates.join("  ")), Print("\r\n"))
+                    {
+                        break Result::Err(error);
+                    }
+                }
+            }
+            _ => input.handle_key(key),
+        }
+    };
+    terminal::disable_raw_mode()?;
+    println!();
+    Result::Ok(result?)
+}
+
+impl Ui {
+    pub fn with_history(history: History) -> Ui {
+        Ui {
+            history,
+            ..Ui::default()
+        }
+    }
+
+    fn event_loop(&mut self, app: &mut App, out: &mut impl Write) -> Result<(), Error> {
+        loop {
+            self.draw(app, out)?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !self.handle_key(app, key) {
+                    return Result::Ok(());
+                }
+            }
+        }
+    }
+
+    // indexes of the entries passing the filter in display order
+    fn rows(&self, app: &App) -> Vec<usize> {
+        let options = ListOptions {
+            filter: self.filter.clone(),
+            ..ListOptions::default()
+        };
+        app.model
+            .list(&options, &app.model.today())
+            .iter()
+            .map(|(index, _)| *index)
+            .collect()
+    }
+
+    /// Applies a key press, returns false when the user quits
+    pub fn handle_key(&mut self, app: &mut App, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return false;
+        }
+        self.message.clear();
+        match std::mem::replace(&mut self.mode, Mode::Browse) {
+            Mode::Browse => return self.browse(app, key),
+            Mode::ConfirmDelete(index) => {
+                if key.code == KeyCode::Char('y') {
+                    self.apply(app, index, Command::Remove);
+                }
+            }
+            Mode::Input(prompt, mut input) => match (key.code, &prompt) {
+                (KeyCode::Enter, Prompt::Filter(_)) => {
+                    self.history.reset();
+                    if !self.filter_text.is_empty() {
+                        if let Result::Err(error) = self.history.push(&self.filter_text) {
+                            self.message = format!("cannot save filter history: {}", error);
+                        }
+                    }
+                }
+                // a rejected task stays in the prompt so it can be corrected
+                (KeyCode::Enter, _) => {
+                    let result = match prompt {
+                        Prompt::Edit(index) => app.model.edit(index, &input.text()).map_err(String::from),
+                        _ => app.model.execute(Command::Add(input.text())).map_err(String::from),
+                    };
+                    match result {
+                        Result::Ok(()) => self.saved(app, Result::Ok(())),
+                        Result::Err(message) => {
+                            self.message = message;
+                            self.mode = Mode::Input(prompt, input);
+                        }
+                    }
+                }
+                (KeyCode::Esc, Prompt::Filter(previous)) => {
+                    self.history.reset();
+                    self.set_filter(&previous.clone());
+                }
+                (KeyCode::Esc, _) => (),
+                (KeyCode::Tab, Prompt::Add) | (KeyCode::Tab, Prompt::Edit(_)) => {
+                    let candidates = app.model.completions(&input.word_before_cursor());
+                    input.complete(&candidates);
+                    if candidates.len() > 1 {
+                        self.message = candidates.join("  ");
+                    }
+                    self.mode = Mode::Input(prompt, input);
+                }
+                (KeyCode::Up, Prompt::Filter(_)) | (KeyCode::Down, Prompt::Filter(_)) => {
+                    let browsed = match key.code {
+                        KeyCode::Up => self.history.previous(),
+                        _ => self.history.next(),
+                    };
+                    if let Option::Some(text) = browsed.map(String::from) {
+                        input = LineInput::new(&text);
+                        self.set_filter(&text);
+                    }
+                    self.mode = Mode::Input(prompt, input);
+                }
+                _ => {
+                    input.handle_key(key);
+                    if let Prompt::Filter(_) = prompt {
+                        self.set_filter(&input.text());
+                    }
+                    self.mode = Mode::Input(prompt, input);
+                }
+            },
+        }
+        true
+    }
+
+    // filters as the text is typed, an unfinished expression keeps the previous filter
+    fn set_filter(&mut self, text: &str) {
+        self.cursor = 0;
+        if text.trim().is_empty() {
+            self.filter = Option::None;
+            self.filter_text.clear();
+            return;
+        }
+        match Filter::parse(text) {
+            Result::Ok(filter) => {
+                self.filter = Option::Some(filter.fuzzy());
+                self.filter_text = text.into();
+            }
+            Result::Err(error) => self.message = format!("{}", error),
+        }
+    }
+
+    fn browse(&mut self, app: &mut App, key: KeyEvent) -> bool {
+        let rows = self.rows(app);
+        self.cursor = self.cursor.min(rows.len().saturating_sub(1));
+        let current = rows.get(self.cursor).copied();
+        match (key.code, current) {
+            (KeyCode::Esc, _) if self.filter.is_some() => self.set_filter(""),
+            (KeyCode::Char('q'), _) | (KeyCode::Esc, _) => return false,
+            (KeyCode::Down, _) | (KeyCode::Char('j'), _) => {
+                self.cursor = (self.cursor + 1).min(rows.len().saturating_sub(1))
+            }
+            (KeyCode::Up, _) | (KeyCode::Char('k'), _) => self.cursor = self.cursor.saturating_sub(1),
+            (KeyCode::Home, _) | (KeyCode::Char('g'), _) => self.cursor = 0,
+            (KeyCode::End, _) | (KeyCode::Char('G'), _) => self.cursor = rows.len().saturating_sub(1),
+            (KeyCode::Char('/'), _) => {
+                let input = LineInput::new(&self.filter_text);
+                self.mode = Mode::Input(Prompt::Filter(self.filter_text.clone()), input);
+            }
+            (KeyCode::Char('a'), _) => self.mode = Mode::Input(Prompt::Add, LineInput::default()),
+            (KeyCode::Char('A'), _) => {
+                let count = app.model.archive_done();
+                self.saved(app, Result::Ok(()));
+                if self.message.is_empty() {
+                    self.message = format!("{} task(s) archived", count);
+                }
+            }
+            (KeyCode::Char(' '), Option::Some(index)) | (KeyCode::Char('x'), Option::Some(index)) => {
+                let command = match app.model.todo_data.entries[index].status {
+                    Status::Open => Command::Do,
+                    Status::Done(_) => Command::Undo,
+                };
+                self.apply(app, index, command);
+            }
+            (KeyCode::Char('e'), Option::Some(index)) => {
+                let line = format!("{}", app.model.todo_data.entries[index]);
+                self.mode = Mode::Input(Prompt::Edit(index), LineInput::new(&line));
+            }
+            (KeyCode::Char('d'), Option::Some(index)) | (KeyCode::Delete, Option::Some(index)) => {
+                self.message = "delete this task? (y/n)".into();
+                self.mode = Mode::ConfirmDelete(index);
+            }
+            _ => (),
+        }
+        true
+    }
+
+    // runs the command on the task at the index, selectors only reach the first 65536 tasks
+    fn apply(&mut self, app: &mut App, index: usize, command: fn(Selector) -> Command) {
+        let result = match u16::try_from(index) {
+            Result::Ok(index) => app.model.execute(command(index.into())).map_err(String::from),
+            Result::Err(_) => Result::Err("only the first 65536 tasks can be changed here".into()),
+        };
+        self.saved(app, result);
+    }
+
+    // saves after a successful change, otherwise shows why it failed
+    fn saved(&mut self, app: &mut App, result: Result<(), String>) {
+        if let Result::Err(message) = result {
+            self.message = message;
+        } else if let Result::Err(error) = app.save_model() {
+            self.message = error.message;
+        }
+    }
+
+    fn draw(&mut self, app: &App, out: &mut impl Write) -> Result<(), Error> {
+        let (_, lines) = terminal::size()?;
+        let lines = lines as usize;
+        // title, message and help or prompt lines
+        let height = lines.saturating_sub(3).max(1);
+        let rows = self.rows(app);
+        self.cursor = self.cursor.min(rows.len().saturating_sub(1));
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+        let today = app.model.today();
+        let renderer = Renderer::for_screen(&app.model.settings.colors);
+        queue!(
+            out,
+            terminal::Clear(ClearType::All),
+            cursor::Hide,
+            cursor::MoveTo(0, 0),
+            SetAttribute(Attribute::Bold),
+            Print(format!("todocommander  {} task(s)", rows.len())),
+            SetAttribute(Attribute::Reset)
+        )?;
+        if !self.filter_text.is_empty() {
+            queue!(out, Print(format!("  filter: {}", self.filter_text)))?;
+        }
+        for (row, index) in rows.iter().enumerate().skip(self.offset).take(height) {
+            queue!(out, cursor::MoveTo(0, (row - self.offset + 1) as u16))?;
+            if row == self.cursor {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+            let entry = &app.model.todo_data.entries[*index];
+            queue!(out, Print(renderer.render(*index, entry, &today)), SetAttribute(Attribute::Reset))?;
+        }
+        queue!(out, cursor::MoveTo(0, lines.saturating_sub(2) as u16), Print(&self.message))?;
+        queue!(out, cursor::MoveTo(0, lines.saturating_sub(1) as u16))?;
+        match &self.mode {
+            Mode::Input(prompt, input) => {
+                let column = u16::try_from(prompt.label().chars().count() + input.position()).unwrap_or(u16::MAX);
+                queue!(
+                    out,
+                    Print(prompt.label()),
+                    Print(input.text()),
+                    cursor::MoveTo(column, lines.saturating_sub(1) as u16),
+                    cursor::Show
+                )?;
+            }
+            _ => queue!(out, Print(HELP))?,
+        }
+        out.flush()?;
+        Result::Ok(())
+    }
+}
@@ -1,19 +1,98 @@
+use crate::model::common::ParsingError;
+use crate::model::ElementKind;
+use crate::model::Filter;
+use crate::model::Priority;
+use crate::model::Selector;
+use crate::model::SortKey;
+
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug)]
 pub struct ArgsConfig {
     pub todo_filename: Option<String>,
     pub done_filename: Option<String>,
+    pub date_on_add: bool,
     pub help: bool,
+    pub lenient: bool,
 }
 
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum Command {
     Add(String),
-    Archive(u16),
-    Do(u16),
-    List,
-    Remove(u16),
-    Undo(u16),
+    // add without text, asks for it
+    AddPrompt,
+    Append(Selector, String),
+    Archive(Option<Selector>),
+    Check,
+    // hidden, prints the candidates for the last of the words
+    Complete(Vec<String>),
+    Completions(Shell),
+    Contexts(DataScope),
+    Depri(Selector),
+    Do(Selector),
+    Edit(Selector),
+    List(ListOptions),
+    Prepend(Selector, String),
+    Pri(Selector, Priority),
+    Projects(DataScope),
+    Remove(Selector),
+    Rename(RenameOptions),
+    Replace(Selector, String),
+    Ui,
+    Undo(Selector),
+}
+
+#[derive(Debug, Default)]
+pub struct ListOptions {
+    pub filter: Option<Filter>,
+    pub sort: Vec<SortKey>,
+    pub future: FutureEntries,
+}
+
+// entries whose threshold date is after today
+#[derive(Debug, PartialEq, Default)]
+pub enum FutureEntries {
+    #[default]
+    Hide,
+    IncludeDays(u16),
+    Show,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RenameOptions {
+    pub kind: ElementKind,
+    pub old: String,
+    pub new: String,
+    // only show the changes
+    pub dry_run: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Shell {
+    Bash,
+    Fish,
+    Zsh,
+}
+
+impl Shell {
+    pub fn parse(name: &str) -> Result<Shell, ParsingError> {
+        match name {
+            "bash" => Result::Ok(Shell::Bash),
+            "fish" => Result::Ok(Shell::Fish),
+            "zsh" => Result::Ok(Shell::Zsh),
+            _ => Result::Err(ParsingError::new("unknown shell, expected bash, fish or zsh")),
+        }
+    }
+}
+
+// data files considered by the projects and contexts commands
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum DataScope {
+    #[default]
+    Todo,
+    All,
 }
 
 #[derive(Debug)]
@@ -31,9 +110,35 @@ impl ArgsConfig {
         self.done_filename = Option::Some(value);
     }
 
+    pub fn toggle_date_on_add(self: &mut ArgsConfig) {
+        self.date_on_add = true;
+    }
+
     pub fn toggle_help(self: &mut ArgsConfig) {
         self.help = true;
     }
+
+    pub fn toggle_lenient(self: &mut ArgsConfig) {
+        self.lenient = true;
+    }
+}
+
+impl Command {
+    // commands that only read do not save the files
+    pub fn modifies_data(&self) -> bool {
+        match self {
+            Command::Check
+            | Command::Complete(_)
+            | Command::Completions(_)
+            | Command::Contexts(_)
+            | Command::List(_)
+            | Command::Projects(_) => false,
+            Command::Rename(options) => !options.dry_run,
+            // saves after every change
+            Command::Ui => false,
+            _ => true,
+        }
+    }
 }
 
 pub enum ArgumentType {
@@ -45,17 +150,144 @@ pub struct ArgumentDef {
     pub long_form: &'static str,
     pub short_form: Option<&'static str>,
     pub help_message: &'static str,
-    accessor: &'static dyn Fn(&mut ArgsConfig, String) -> (),
+    accessor: &'static dyn Fn(&mut ArgsConfig, String),
 }
 
 pub struct FlagDef {
     pub long_form: &'static str,
     pub short_form: Option<&'static str>,
     pub help_message: &'static str,
-    accessor: &'static dyn Fn(&mut ArgsConfig) -> (),
+    accessor: &'static dyn Fn(&mut ArgsConfig),
 }
 
-fn find_arg_def<'a>(
+pub struct CommandDef {
+    pub name: &'static str,
+    pub help_message: &'static str,
+    // the arguments start with a task selector
+    pub selector: bool,
+    // flags offered when completing, args/tests.rs checks them against the parsers
+    pub flags: &'static [&'static str],
+}
+
+// every command parse_command knows, except the hidden __complete
+pub const COMMAND_DEFS: &[CommandDef] = &[
+    CommandDef {
+        name: "add",
+        help_message: "add a task",
+        selector: false,
+        flags: &[],
+    },
+    CommandDef {
+        name: "append",
+        help_message: "add text to the end of tasks",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "archive",
+        help_message: "move tasks to the done file",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "check",
+        help_message: "report lines that cannot be parsed",
+        selector: false,
+        flags: &[],
+    },
+    CommandDef {
+        name: "completions",
+        help_message: "print a shell completion script",
+        selector: false,
+        flags: &[],
+    },
+    CommandDef {
+        name: "contexts",
+        help_message: "count tasks by context",
+        selector: false,
+        flags: &["--done"],
+    },
+    CommandDef {
+        name: "depri",
+        help_message: "remove the priority of tasks",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "do",
+        help_message: "mark tasks as done",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "edit",
+        help_message: "change a task in the editor setting or $EDITOR",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "list",
+        help_message: "list tasks",
+        selector: false,
+        flags: &["--sort", "--all", "--include-future"],
+    },
+    CommandDef {
+        name: "mv-context",
+        help_message: "rename a context",
+        selector: false,
+        flags: &["--dry-run"],
+    },
+    CommandDef {
+        name: "mv-project",
+        help_message: "rename a project",
+        selector: false,
+        flags: &["--dry-run"],
+    },
+    CommandDef {
+        name: "prepend",
+        help_message: "add text to the start of tasks",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "pri",
+        help_message: "set the priority of tasks",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "projects",
+        help_message: "count tasks by project",
+        selector: false,
+        flags: &["--done"],
+    },
+    CommandDef {
+        name: "remove",
+        help_message: "delete tasks",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "replace",
+        help_message: "replace the text of a task",
+        selector: true,
+        flags: &[],
+    },
+    CommandDef {
+        name: "ui",
+        help_message: "open the full screen interface",
+        selector: false,
+        flags: &[],
+    },
+    CommandDef {
+        name: "undo",
+        help_message: "mark tasks as not done",
+        selector: true,
+        flags: &[],
+    },
+];
+
+pub fn find_arg_def<'a>(
     arg: &str,
     argument_defs_accessors: &'a [ArgumentType],
 ) -> Option<&'a ArgumentType> {
@@ -81,13 +313,25 @@ fn find_arg_def<'a>(
     Option::None
 }
 
-const ARGUMENT_DEFS: &'static [ArgumentType] = &[
+pub const ARGUMENT_DEFS: &[ArgumentType] = &[
     ArgumentType::Flag(FlagDef {
         long_form: "--help",
         short_form: Option::None,
         help_message: "help",
         accessor: &ArgsConfig::toggle_help,
     }),
+    ArgumentType::Flag(FlagDef {
+        long_form: "--date-on-add",
+        short_form: Option::Some("-t"),
+        help_message: "prefix the creation date to added tasks",
+        accessor: &ArgsConfig::toggle_date_on_add,
+    }),
+    ArgumentType::Flag(FlagDef {
+        long_form: "--lenient",
+        short_form: Option::None,
+        help_message: "keep lines that cannot be parsed as they are",
+        accessor: &ArgsConfig::toggle_lenient,
+    }),
     ArgumentType::Parameterized(ArgumentDef {
         long_form: "--todo-file",
         short_form: Option::Some("-f"),
@@ -105,6 +349,7 @@ const ARGUMENT_DEFS: &'static [ArgumentType] = &[
 pub enum ErrorType {
     MissingArguments(Vec<&'static ArgumentDef>),
     CannotIdentifyCommand(Vec<String>),
+    InvalidArgument(ParsingError),
 }
 
 pub fn parse_config(
@@ -113,51 +358,156 @@ pub fn parse_config(
     let mut config = ArgsConfig {
         todo_filename: Option::None,
         done_filename: Option::None,
+        date_on_add: false,
         help: false,
+        lenient: false,
     };
 
     let mut unprocessed_args: Vec<String> = Vec::new();
     while let Option::Some(arg) = args.next() {
-        if let Option::Some(arg_type) = find_arg_def(&arg, &ARGUMENT_DEFS) {
-            let argument = args.next();
+        if let Option::Some(arg_type) = find_arg_def(&arg, ARGUMENT_DEFS) {
             match arg_type {
-                ArgumentType::Parameterized(arg_def) => (arg_def.accessor)(
-                    &mut config,
-                    argument.expect(&format!("argument {} not present", &arg)),
-                ),
+                ArgumentType::Parameterized(arg_def) => match args.next() {
+                    Option::Some(value) => (arg_def.accessor)(&mut config, value),
+                    Option::None => return Result::Err(ErrorType::MissingArguments(vec![arg_def])),
+                },
                 ArgumentType::Flag(flag_def) => (flag_def.accessor)(&mut config),
             }
         } else {
             unprocessed_args.push(arg);
-            while let Option::Some(arg) = args.next() {
-                unprocessed_args.push(arg);
-            }
+            unprocessed_args.extend(args);
             break;
         }
     }
     Result::Ok((config, unprocessed_args))
 }
 
+fn parse_list_options(args: &[String]) -> Result<ListOptions, ErrorType> {
+    let mut options = ListOptions::default();
+    let mut filter_args: Vec<&str> = Vec::new();
+    let mut args = args.iter();
+    while let Option::Some(arg) = args.next() {
+        if arg == "--sort" {
+            let keys = args.next().ok_or(ErrorType::InvalidArgument(ParsingError::new("missing sort keys")))?;
+            options.sort = SortKey::parse_list(keys).map_err(ErrorType::InvalidArgument)?;
+        } else if arg == "--all" {
+            options.future = FutureEntries::Show;
+        } else if arg == "--include-future" {
+            let days = args.next().and_then(|days| days.parse::<u16>().ok()).ok_or(
+                ErrorType::InvalidArgument(ParsingError::new("error parsing number of days")),
+            )?;
+            options.future = FutureEntries::IncludeDays(days);
+        } else {
+            filter_args.push(arg);
+        }
+    }
+    if !filter_args.is_empty() {
+        options.filter = Option::Some(Filter::parse(&filter_args.join(" ")).map_err(ErrorType::InvalidArgument)?);
+    }
+    Result::Ok(options)
+}
+
+// the selector is a single argument, a filter with spaces has to be quoted
+fn parse_selector(args: &[String]) -> Result<Selector, ErrorType> {
+    match args {
+        [selector] => Selector::parse(selector).map_err(ErrorType::InvalidArgument),
+        [] => Result::Err(ErrorType::InvalidArgument(ParsingError::new("missing task selector"))),
+        _ => Result::Err(ErrorType::InvalidArgument(ParsingError::new(
+            "expected a single task selector, separate indexes with commas",
+        ))),
+    }
+}
+
+// --done adds the entries of the done file
+fn parse_scope(command: &[String]) -> Result<DataScope, ErrorType> {
+    match &command[1..] {
+        [] => Result::Ok(DataScope::Todo),
+        [flag] if flag == "--done" => Result::Ok(DataScope::All),
+        _ => Result::Err(ErrorType::CannotIdentifyCommand(command.to_owned())),
+    }
+}
+
+// [--dry-run] OLD NEW, names are accepted with or without their + or @ prefix
+fn parse_rename_options(kind: ElementKind, command: &[String]) -> Result<RenameOptions, ErrorType> {
+    let (dry_run, names) = match &command[1..] {
+        [flag, names @ ..] if flag == "--dry-run" => (true, names),
+        names => (false, names),
+    };
+    match names {
+        [old, new] => {
+            let old = old.strip_prefix(kind.prefix()).unwrap_or(old);
+            let new = new.strip_prefix(kind.prefix()).unwrap_or(new);
+            if old.is_empty() || new.is_empty() {
+                return Result::Err(ErrorType::InvalidArgument(ParsingError::new("empty name")));
+            }
+            Result::Ok(RenameOptions {
+                kind,
+                old: old.into(),
+                new: new.into(),
+                dry_run,
+            })
+        }
+        _ => Result::Err(ErrorType::CannotIdentifyCommand(command.to_owned())),
+    }
+}
+
+// the selector is a single argument, the text is made of everything after it
+fn parse_selector_and_text(command: &[String]) -> Result<(Selector, String), ErrorType> {
+    match command {
+        [_, selector, text @ ..] if !text.is_empty() => {
+            Result::Ok((parse_selector(std::slice::from_ref(selector))?, text.join(" ")))
+        }
+        _ => Result::Err(ErrorType::CannotIdentifyCommand(command.to_owned())),
+    }
+}
+
 pub fn parse_command(command: &Vec<String>) -> Result<Option<Command>, ErrorType> {
-    if command.len() > 0 {
+    if !command.is_empty() {
         match command[0].as_str() {
+            "__complete" => Result::Ok(Option::Some(Command::Complete(command[1..].to_vec()))),
+            "add" if command.len() == 1 => Result::Ok(Option::Some(Command::AddPrompt)),
             "add" => Result::Ok(Option::Some(Command::Add(command[1..].join(" ")))),
+            "append" => parse_selector_and_text(command)
+                .map(|(selector, text)| Option::Some(Command::Append(selector, text))),
             "archive" => {
-                let id = command[1].parse::<u16>().expect("error parsing task id");
-                Result::Ok(Option::Some(Command::Archive(id)))
-            }
-            "do" => {
-                let id = command[1].parse::<u16>().expect("error parsing task id");
-                Result::Ok(Option::Some(Command::Do(id)))
-            }
-            "list" => Result::Ok(Option::Some(Command::List)),
-            "remove" => { 
-                let id = command[1].parse::<u16>().expect("error parsing task id");
-                Result::Ok(Option::Some(Command::Remove(id))) }
-            "undo" => {
-                let id = command[1].parse::<u16>().expect("error parsing task id");
-                Result::Ok(Option::Some(Command::Undo(id)))
+                let selector = if command.len() > 1 {
+                    Option::Some(parse_selector(&command[1..])?)
+                } else {
+                    Option::None
+                };
+                Result::Ok(Option::Some(Command::Archive(selector)))
             }
+            "check" => Result::Ok(Option::Some(Command::Check)),
+            "completions" => match &command[1..] {
+                [shell] => Shell::parse(shell)
+                    .map(|shell| Option::Some(Command::Completions(shell)))
+                    .map_err(ErrorType::InvalidArgument),
+                _ => Result::Err(ErrorType::CannotIdentifyCommand(command.to_owned())),
+            },
+            "contexts" => parse_scope(command).map(|scope| Option::Some(Command::Contexts(scope))),
+            "depri" => Result::Ok(Option::Some(Command::Depri(parse_selector(&command[1..])?))),
+            "do" => Result::Ok(Option::Some(Command::Do(parse_selector(&command[1..])?))),
+            "edit" => Result::Ok(Option::Some(Command::Edit(parse_selector(&command[1..])?))),
+            "list" => parse_list_options(&command[1..]).map(|options| Option::Some(Command::List(options))),
+            "mv-context" => parse_rename_options(ElementKind::Context, command)
+                .map(|options| Option::Some(Command::Rename(options))),
+            "mv-project" => parse_rename_options(ElementKind::Project, command)
+                .map(|options| Option::Some(Command::Rename(options))),
+            "prepend" => parse_selector_and_text(command)
+                .map(|(selector, text)| Option::Some(Command::Prepend(selector, text))),
+            "pri" => match command.split_last() {
+                Option::Some((letter, [_, selector @ ..])) if !selector.is_empty() => {
+                    let priority = Priority::from_letter(letter).map_err(ErrorType::InvalidArgument)?;
+                    Result::Ok(Option::Some(Command::Pri(parse_selector(selector)?, priority)))
+                }
+                _ => Result::Err(ErrorType::CannotIdentifyCommand(command.to_owned())),
+            },
+            "projects" => parse_scope(command).map(|scope| Option::Some(Command::Projects(scope))),
+            "remove" => Result::Ok(Option::Some(Command::Remove(parse_selector(&command[1..])?))),
+            "replace" => parse_selector_and_text(command)
+                .map(|(selector, text)| Option::Some(Command::Replace(selector, text))),
+            "ui" => Result::Ok(Option::Some(Command::Ui)),
+            "undo" => Result::Ok(Option::Some(Command::Undo(parse_selector(&command[1..])?))),
             _ => Result::Err(ErrorType::CannotIdentifyCommand(command.to_owned())),
         }
     } else {
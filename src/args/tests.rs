@@ -1,55 +1,62 @@
+#[allow(dead_code)]
+fn parse(words: &[&str]) -> Option<super::Command> {
+    let command: Vec<String> = words.iter().map(|s| String::from(*s)).collect();
+    match super::parse_command(&command) {
+        Result::Ok(command) => command,
+        Result::Err(_) => panic!("cannot parse {:?}", words),
+    }
+}
+
 #[test]
-pub fn parse_erroneous_arguments_1() {
+pub fn parse_single_file_argument() {
     use super::*;
-    let parameters: Vec<&str> = vec!["--todo-file", "a"];
+    let parameters: Vec<&str> = vec!["tcd", "--todo-file", "a"];
+    let iter: &mut dyn Iterator<Item = String> = &mut parameters.iter().map(|s| String::from(*s));
+    assert!(matches!(
+        parse_arguments(iter),
+        Result::Ok(Arguments {
+            config: ArgsConfig {
+                todo_filename: Option::Some(a),
+                done_filename: Option::None,
+                ..
+            },
+            command: Option::None,
+        }) if a == "a"
+    ));
+}
+
+#[test]
+pub fn parse_erroneous_command() {
+    use super::*;
+    let parameters: Vec<&str> = vec!["tcd", "--todo-file", "a", "frobnicate"];
     let iter: &mut dyn Iterator<Item = String> = &mut parameters.iter().map(|s| String::from(*s));
     match parse_arguments(iter) {
-        Result::Err(ErrorType::MissingArguments(missing_arguments_vector)) => assert!(matches!(
-            missing_arguments_vector
-                .iter()
-                .find(|arg_def| match arg_def {
-                    ArgumentDef {
-                        long_form: "--done-file",
-                        ..
-                    } => true,
-                    _ => false,
-                }),
-            Option::Some(_)
-        )),
-        _ => {
-            panic!("This test failed");
-        }
+        Result::Err(ErrorType::CannotIdentifyCommand(command)) => assert_eq!(command, vec!["frobnicate"]),
+        _ => panic!("This test failed"),
     }
 }
 
 #[test]
-pub fn parse_erroneous_arguments_2() {
+pub fn parse_erroneous_arguments_missing_value() {
     use super::*;
-    let parameters: Vec<&str> = vec!["--done-file", "a"];
+    let parameters: Vec<&str> = vec!["tcd", "-f", "todo.txt", "-d"];
     let iter: &mut dyn Iterator<Item = String> = &mut parameters.iter().map(|s| String::from(*s));
     match parse_arguments(iter) {
         Result::Err(ErrorType::MissingArguments(missing_arguments_vector)) => assert!(matches!(
-            missing_arguments_vector
-                .iter()
-                .find(|arg_def| match arg_def {
-                    ArgumentDef {
-                        long_form: "--todo-file",
-                        ..
-                    } => true,
-                    _ => false,
-                }),
-            Option::Some(_)
+            missing_arguments_vector.as_slice(),
+            [ArgumentDef {
+                long_form: "--done-file",
+                ..
+            }]
         )),
-        _ => {
-            panic!("This test failed");
-        }
+        _ => panic!("This test failed"),
     }
 }
 
 #[test]
 pub fn parse_arguments_1() {
     use super::*;
-    let parameters: Vec<&str> = vec!["--done-file", "a", "--todo-file", "b", "do", "36"];
+    let parameters: Vec<&str> = vec!["tcd", "--done-file", "a", "--todo-file", "b", "do", "36"];
     let iter: &mut dyn Iterator<Item = String> = &mut parameters.iter().map(|s| String::from(*s));
     assert!(matches!(
         parse_arguments(iter),
@@ -57,16 +64,17 @@ pub fn parse_arguments_1() {
             config: ArgsConfig {
                 todo_filename: Option::Some(b),
                 done_filename: Option::Some(a),
+                ..
             },
-            command: Command::Do(36),
-        }) if a == "a" && b == "b"
+            command: Option::Some(Command::Do(Selector::Indexes(indexes))),
+        }) if a == "a" && b == "b" && indexes == vec![36]
     ));
 }
 
 #[test]
 pub fn parse_arguments_2() {
     use super::*;
-    let parameters = vec!["--done-file", "a", "--todo-file", "b", "add", "foo bar", "baz"];
+    let parameters: Vec<&str> = vec!["tcd", "--done-file", "a", "--todo-file", "b", "add", "foo bar", "baz"];
     let iter: &mut dyn Iterator<Item = String> = &mut parameters.iter().map(|s| String::from(*s));
     assert!(matches!(
         parse_arguments(iter),
@@ -74,8 +82,9 @@ pub fn parse_arguments_2() {
             config: ArgsConfig {
                 todo_filename: Option::Some(b),
                 done_filename: Option::Some(a),
+                ..
             },
-            command: Command::Add(text),
+            command: Option::Some(Command::Add(text)),
         }) if a == "a" && b == "b" && text == "foo bar baz"
     ));
 }
@@ -83,10 +92,124 @@ pub fn parse_arguments_2() {
 #[test]
 pub fn parse_config_unprocessed_args() {
     use super::*;
-    let parameters: Vec<&str> = vec!["--done-file", "a", "--todo-file", "b", "do", "36"];
+    let parameters: Vec<&str> = vec!["--done-file", "a", "--todo-file", "b", "-t", "do", "36"];
     let iter: &mut dyn Iterator<Item = String> = &mut parameters.iter().map(|s| String::from(*s));
     match parse_config(iter) {
-        Result::Ok((_, unprocessed_args)) if unprocessed_args == vec!["do", "36"] => (),
+        Result::Ok((config, unprocessed_args)) if unprocessed_args == vec!["do", "36"] => assert!(config.date_on_add),
         _ => panic!("test failed"),
     }
 }
+
+#[test]
+pub fn parse_list_options() {
+    use super::*;
+    match parse(&["list", "--sort", "due,-priority", "--all", "+work"]) {
+        Option::Some(Command::List(options)) => {
+            assert_eq!(options.sort, SortKey::parse_list("due,-priority").unwrap());
+            assert_eq!(options.future, FutureEntries::Show);
+            assert!(options.filter.is_some());
+        }
+        command => panic!("unexpected command {:?}", command),
+    }
+    assert!(matches!(
+        parse(&["list", "--include-future", "3"]),
+        Option::Some(Command::List(ListOptions {
+            filter: Option::None,
+            future: FutureEntries::IncludeDays(3),
+            ..
+        }))
+    ));
+}
+
+#[test]
+pub fn parse_rename_dry_run() {
+    use super::*;
+    assert!(matches!(
+        parse(&["mv-project", "--dry-run", "+work", "job"]),
+        Option::Some(Command::Rename(RenameOptions {
+            kind: ElementKind::Project,
+            dry_run: true,
+            ..
+        }))
+    ));
+    match parse(&["mv-context", "@phone", "@call"]) {
+        Option::Some(Command::Rename(options)) => assert_eq!(
+            options,
+            RenameOptions {
+                kind: ElementKind::Context,
+                old: "phone".into(),
+                new: "call".into(),
+                dry_run: false,
+            }
+        ),
+        command => panic!("unexpected command {:?}", command),
+    }
+}
+
+#[test]
+pub fn parse_scope_with_done() {
+    use super::*;
+    assert!(matches!(parse(&["projects"]), Option::Some(Command::Projects(DataScope::Todo))));
+    assert!(matches!(parse(&["projects", "--done"]), Option::Some(Command::Projects(DataScope::All))));
+    assert!(matches!(parse(&["contexts", "--done"]), Option::Some(Command::Contexts(DataScope::All))));
+}
+
+#[test]
+pub fn parse_commands_without_text() {
+    use super::*;
+    assert!(matches!(
+        parse(&["edit", "2"]),
+        Option::Some(Command::Edit(Selector::Indexes(indexes))) if indexes == vec![2]
+    ));
+    assert!(matches!(parse(&["check"]), Option::Some(Command::Check)));
+    assert!(matches!(parse(&["add"]), Option::Some(Command::AddPrompt)));
+    assert!(matches!(parse(&["completions", "zsh"]), Option::Some(Command::Completions(Shell::Zsh))));
+    assert!(matches!(
+        parse_command(&vec!["completions".into(), "tcsh".into()]),
+        Result::Err(ErrorType::InvalidArgument(_))
+    ));
+}
+
+// one invocation for every flag a command parser handles, the completion flags must match them
+#[allow(dead_code)]
+const FLAG_SAMPLES: &[(&str, &[&str])] = &[
+    ("--done", &["contexts", "--done"]),
+    ("--sort", &["list", "--sort", "due"]),
+    ("--all", &["list", "--all"]),
+    ("--include-future", &["list", "--include-future", "3"]),
+    ("--dry-run", &["mv-context", "--dry-run", "@a", "@b"]),
+    ("--dry-run", &["mv-project", "--dry-run", "+a", "+b"]),
+    ("--done", &["projects", "--done"]),
+];
+
+#[test]
+pub fn command_flags_match_their_parsers() {
+    use super::*;
+    for def in COMMAND_DEFS {
+        for flag in def.flags {
+            let sample = FLAG_SAMPLES.iter().find(|(sample_flag, words)| sample_flag == flag && words[0] == def.name);
+            match sample {
+                Option::Some((_, words)) => assert!(parse(words).is_some()),
+                Option::None => panic!("no sample for {} {}", def.name, flag),
+            }
+        }
+    }
+    for (flag, words) in FLAG_SAMPLES {
+        let def = COMMAND_DEFS.iter().find(|def| def.name == words[0]).unwrap();
+        assert!(def.flags.contains(flag), "{} is not offered for {}", flag, def.name);
+    }
+}
+
+#[test]
+pub fn parse_rejects_several_selectors() {
+    use super::*;
+    for command in &["remove", "do", "undo", "archive", "depri", "edit"] {
+        let words: Vec<String> = vec![command.to_string(), "1".into(), "2".into()];
+        assert!(matches!(parse_command(&words), Result::Err(ErrorType::InvalidArgument(_))));
+    }
+    assert!(matches!(
+        parse_command(&vec!["pri".into(), "1".into(), "2".into(), "A".into()]),
+        Result::Err(ErrorType::InvalidArgument(_))
+    ));
+    assert!(matches!(parse(&["remove", "1,2"]), Option::Some(Command::Remove(Selector::Indexes(_)))));
+}
@@ -0,0 +1,180 @@
+use crate::args::find_arg_def;
+use crate::args::parse_config;
+use crate::args::ArgsConfig;
+use crate::args::ArgumentType;
+use crate::args::Shell;
+use crate::args::ARGUMENT_DEFS;
+use crate::args::COMMAND_DEFS;
+use crate::model::Model;
+
+/*
+ * The scripts only hand the words of the command line to the hidden __complete command, so the
+ * options and commands they offer always match the ones the program parses. Candidates come one
+ * per line, optionally followed by a tab and a description.
+ */
+const BASH_SCRIPT: &str = r#"_PROGRAM_complete() {
+    local cur words cword
+    if declare -F _get_comp_words_by_ref >/dev/null; then
+        _get_comp_words_by_ref -n : cur words cword
+    else
+        cur="${COMP_WORDS[COMP_CWORD]}"
+        words=("${COMP_WORDS[@]}")
+        cword=$COMP_CWORD
+    fi
+    local IFS=$'\n'
+    COMPREPLY=($(PROGRAM __complete "${words[@]:1:cword}" 2>/dev/null))
+    COMPREPLY=("${COMPREPLY[@]%%$'\t'*}")
+    if declare -F __ltrim_colon_completions >/dev/null; then
+        __ltrim_colon_completions "$cur"
+    fi
+}
+complete -o default -F _PROGRAM_complete PROGRAM
+"#;
+
+const ZSH_SCRIPT: &str = r#"#compdef PROGRAM
+_PROGRAM_complete() {
+    local -a candidates values
+    candidates=("${(@f)$(PROGRAM __complete "${(@)words[2,CURRENT]}" 2>/dev/null)}")
+    candidates=(${candidates:#})
+    # tag keys expect a value, no space is added after them
+    values=(${(M)candidates:#*:})
+    candidates=(${candidates:#*:})
+    candidates=("${(@)candidates//:/\\:}")
+    candidates=("${(@)candidates//$'\t'/:}")
+    _describe -t candidates PROGRAM candidates
+    compadd -S '' -a values
+}
+compdef _PROGRAM_complete PROGRAM
+"#;
+
+const FISH_SCRIPT: &str = r#"function __PROGRAM_complete
+    set -l words (commandline -opc)
+    set -e words[1]
+    set -l current (commandline -ct)
+    PROGRAM __complete $words "$current" 2>/dev/null
+end
+complete -c PROGRAM -f -a '(__PROGRAM_complete)'
+"#;
+
+pub fn script(shell: Shell, program: &str) -> String {
+    let template = match shell {
+        Shell::Bash => BASH_SCRIPT,
+        Shell::Fish => FISH_SCRIPT,
+        Shell::Zsh => ZSH_SCRIPT,
+    };
+    template.replace("PROGRAM", program)
+}
+
+// name the program was started with, the scripts complete that name
+pub fn program_name() -> String {
+    std::env::args()
+        .next()
+        .and_then(|path| {
+            std::path::Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "tc".into())
+}
+
+// options typed before the word being completed, so -f and -d complete from the given files
+pub fn typed_config(words: &[String]) -> Option<ArgsConfig> {
+    let previous = words.split_last().map_or(words, |(_, previous)| previous);
+    parse_config(&mut previous.iter().cloned()).ok().map(|(config, _)| config)
+}
+
+fn candidate(word: &str, description: &str) -> String {
+    format!("{}\t{}", word, description)
+}
+
+/*
+ * Candidates for the last word given the words before it: options, commands, their flags, task
+ * indexes where a selector is expected, then projects, contexts, tag keys and dates. Without a
+ * model only options and commands are offered.
+ */
+pub fn complete(words: &[String], model: Option<&Model>) -> Vec<String> {
+    let (current, previous) = match words.split_last() {
+        Option::Some((current, previous)) => (current.as_str(), previous),
+        Option::None => ("", words),
+    };
+    // values of the file options are left to the shell
+    if let Option::Some(ArgumentType::Parameterized(_)) = previous.last().and_then(|word| find_arg_def(word, ARGUMENT_DEFS))
+    {
+        return Vec::new();
+    }
+    let mut rest = previous.iter();
+    let mut command = Option::None;
+    while let Option::Some(word) = rest.next() {
+        match find_arg_def(word, ARGUMENT_DEFS) {
+            Option::Some(ArgumentType::Parameterized(_)) => {
+                rest.next();
+            }
+            Option::Some(ArgumentType::Flag(_)) => (),
+            Option::None => {
+                command = Option::Some(word.as_str());
+                break;
+            }
+        }
+    }
+    let command = match command {
+        Option::Some(name) => COMMAND_DEFS.iter().find(|def| def.name == name),
+        Option::None if current.starts_with('-') => {
+            return ARGUMENT_DEFS
+                .iter()
+                .flat_map(|arg_type| {
+                    let (long_form, short_form, help_message) = match arg_type {
+                        ArgumentType::Parameterized(def) => (def.long_form, def.short_form, def.help_message),
+                        ArgumentType::Flag(def) => (def.long_form, def.short_form, def.help_message),
+                    };
+                    std::iter::once(long_form)
+                        .chain(short_form)
+                        .filter(|form| form.starts_with(current))
+                        .map(move |form| candidate(form, help_message))
+                        .collect::<Vec<String>>()
+                })
+                .collect();
+        }
+        Option::None => {
+            return COMMAND_DEFS
+                .iter()
+                .filter(|def| def.name.starts_with(current))
+                .map(|def| candidate(def.name, def.help_message))
+                .collect();
+        }
+    };
+    let command = match command {
+        Option::Some(command) => command,
+        Option::None => return Vec::new(),
+    };
+    if current.starts_with('-') {
+        return command
+            .flags
+            .iter()
+            .filter(|flag| flag.starts_with(current))
+            .map(|flag| flag.to_string())
+            .collect();
+    }
+    if command.name == "completions" {
+        return ["bash", "fish", "zsh"]
+            .iter()
+            .filter(|shell| shell.starts_with(current))
+            .map(|shell| shell.to_string())
+            .collect();
+    }
+    let model = match model {
+        Option::Some(model) => model,
+        Option::None => return Vec::new(),
+    };
+    if command.selector && current.chars().all(|c| c.is_ascii_digit()) {
+        return model
+            .todo_data
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (index.to_string(), entry))
+            .filter(|(index, _)| index.starts_with(current))
+            .map(|(index, entry)| candidate(&index, &format!("{}", entry)))
+            .collect();
+    }
+    model.completions(current)
+}
@@ -1,7 +1,17 @@
+use crate::model::Colors;
+
 #[derive(Debug)]
 pub struct Config {
     pub todo_filename: Option<String>,
     pub done_filename: Option<String>,
+    pub holidays_filename: Option<String>,
+    pub history_filename: Option<String>,
+    // command the edit command runs, $EDITOR or vi when not set
+    pub editor: Option<String>,
+    pub auto_uuid: bool,
+    pub date_on_add: bool,
+    pub lenient: bool,
+    pub colors: Colors,
 }
 
 impl Config {
@@ -9,6 +19,13 @@ impl Config {
         Config {
             todo_filename: Option::None,
             done_filename: Option::None,
+            holidays_filename: Option::None,
+            history_filename: Option::None,
+            editor: Option::None,
+            auto_uuid: false,
+            date_on_add: false,
+            lenient: false,
+            colors: Colors::default(),
         }
     }
 }
@@ -24,6 +41,28 @@ pub fn read_configuration_from_filecontent(file_content: &str, result: &mut Conf
             if split[0] == "done_filename" {
                 result.done_filename = Option::Some(split[1].to_owned());
             }
+            if split[0] == "holidays_filename" {
+                result.holidays_filename = Option::Some(split[1].to_owned());
+            }
+            if split[0] == "history_filename" {
+                result.history_filename = Option::Some(split[1].to_owned());
+            }
+            if split[0] == "editor" {
+                result.editor = Option::Some(split[1].to_owned());
+            }
+            if split[0] == "auto_uuid" {
+                result.auto_uuid = split[1] == "true";
+            }
+            if split[0] == "date_on_add" {
+                result.date_on_add = split[1] == "true";
+            }
+            if split[0] == "lenient" {
+                result.lenient = split[1] == "true";
+            }
+            // unknown color names are ignored like unknown settings
+            if let Option::Some(name) = split[0].strip_prefix("color_") {
+                let _ = result.colors.set(name, split[1]);
+            }
         }
     })
 }
@@ -1,19 +1,28 @@
 use super::Error;
 use crate::args::ErrorType; 
+use crate::model::common::ParsingError;
 
 impl std::convert::From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
-        return Error {
+        Error {
             message: format!("{}", error),
-        };
+        }
     }
 }
 
 impl std::convert::From<&str> for Error {
     fn from(error: &str) -> Self {
-        return Error {
+        Error {
+            message: error.to_owned(),
+        }
+    }
+}
+
+impl std::convert::From<ParsingError> for Error {
+    fn from(error: ParsingError) -> Self {
+        Error {
             message: format!("{}", error),
-        };
+        }
     }
 }
 
@@ -22,7 +31,7 @@ impl std::convert::From<ErrorType> for Error {
         match error {
             ErrorType::MissingArguments(unset_arguments) => {
                 for unset_argument in unset_arguments {
-                    eprintln!("error unset argument {}", unset_argument.long_form);
+                    eprintln!("error missing value for argument {}", unset_argument.long_form);
                 }
                 Error {
                     message: "missing arguments".into(),
@@ -31,6 +40,7 @@ impl std::convert::From<ErrorType> for Error {
             ErrorType::CannotIdentifyCommand(_) => Error {
                 message: "error cannot identify command".into(),
             },
+            ErrorType::InvalidArgument(parsing_error) => Error::from(parsing_error),
         }
     }
 }
\ No newline at end of file
@@ -6,44 +6,83 @@ mod tests;
 mod args;
 mod error_conversion;
 mod app;
+mod completions;
 
 use config::*;
 use std::env;
 use std::io::prelude::*;
 use args::parse_arguments;
+use args::Command;
 
 #[derive(Debug)]
 pub struct Error {
+    #[allow(dead_code)] // reported through Debug when main returns
     message: String,
 }
 
-fn read_configuration(mut config: &mut Config) {
+fn read_configuration(config: &mut Config, verbose: bool) {
     if let Option::Some(dirs) = directories::ProjectDirs::from("", "", "todocommander") {
+        let history_path = dirs.data_dir().join("filter_history");
+        config.history_filename = Option::Some(history_path.to_string_lossy().into());
         let mut path = dirs.config_dir().to_path_buf();
         path.push("todocommander.cfg");
-        println!("{:?}", path);
+        if verbose {
+            println!("{:?}", path);
+        }
         if let Result::Ok(mut f) = std::fs::File::open(path) {
             let mut file_content = String::new();
             let _result = f.read_to_string(&mut file_content);
-            read_configuration_from_filecontent(&file_content, &mut config);
+            read_configuration_from_filecontent(&file_content, config);
         }
     }
 }
 
 fn main() -> Result<(), Error> {
-    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
-    println!("{}", VERSION);
-    let mut config = Config::new();
-    read_configuration(&mut config);
-    println!("{:?}", &config);
+    const VERSION: &str = env!("CARGO_PKG_VERSION");
     let arguments = parse_arguments(&mut env::args())?;
+    // the shell reads the completion output, nothing else may be printed
+    let completing = matches!(
+        arguments.command,
+        Option::Some(Command::Complete(_)) | Option::Some(Command::Completions(_))
+    );
+    if !completing {
+        println!("{}", VERSION);
+    }
+    let mut config = Config::new();
+    read_configuration(&mut config, !completing);
+    if !completing {
+        println!("{:?}", &config);
+    }
+    let checking = matches!(arguments.command, Option::Some(Command::Check));
     config = Config {
         todo_filename: arguments.config.todo_filename.or(config.todo_filename),
         done_filename: arguments.config.done_filename.or(config.done_filename),
+        date_on_add: arguments.config.date_on_add || config.date_on_add,
+        lenient: arguments.config.lenient || config.lenient || checking || completing,
+        ..config
     };
+    if let Option::Some(Command::Completions(shell)) = arguments.command {
+        print!("{}", completions::script(shell, &completions::program_name()));
+        return Result::Ok(());
+    }
+    if let Option::Some(Command::Complete(words)) = &arguments.command {
+        if let Option::Some(typed) = completions::typed_config(words) {
+            config = Config {
+                todo_filename: typed.todo_filename.or(config.todo_filename),
+                done_filename: typed.done_filename.or(config.done_filename),
+                ..config
+            };
+        }
+        // without readable files only options and commands are completed
+        let app = app::App::new(config).ok();
+        for candidate in completions::complete(words, app.as_ref().map(app::App::model)) {
+            println!("{}", candidate);
+        }
+        return Result::Ok(());
+    }
     let mut app = app::App::new(config)?;
-    if arguments.config.help == true {
-        Result::Ok(println!("
+    if arguments.config.help {
+        println!("
 TodoCommander
 
 USAGE:
@@ -52,18 +91,52 @@ USAGE:
 OPTIONS:
     -f, --todo-file         Todo filename
     -d, --done-file         Done filename
+    -t, --date-on-add       Prefix the creation date to added tasks
+    --lenient               Keep lines that cannot be parsed as they are
     --help                  This help message
 
 Commands
-    add TEXT 
-    archive [ NUMBER ]
-    do NUMBER
-    list
-    remove NUMBER
-        "))
-    } else if arguments.command.is_some() {
-        app.execute(arguments.command.unwrap())?;
-        app.save_model()
+    add [ TEXT ]
+    append SELECTOR TEXT
+    archive [ SELECTOR ]
+    check
+    completions bash|fish|zsh
+    contexts [ --done ]
+    depri SELECTOR
+    do SELECTOR
+    edit SELECTOR
+    mv-context [ --dry-run ] OLD NEW
+    mv-project [ --dry-run ] OLD NEW
+    list [ --sort KEYS ] [ --all | --include-future DAYS ] [ FILTER ]
+    prepend SELECTOR TEXT
+    pri SELECTOR PRIORITY
+    projects [ --done ]
+    remove SELECTOR
+    replace SELECTOR TEXT
+    ui
+    undo SELECTOR
+
+SELECTOR
+    3, 1,4,7, 2-9, uuid:UUID or a FILTER of +project, @context and key:value terms such as +work,
+    free text needs a leading slash such as /call, quote selectors with spaces
+
+DATES
+    due: and t: accept today, tomorrow, weekday names, +Nd, +Nb, +Nw, +Nm, +Ny, eom and next-month
+        ");
+        Result::Ok(())
+    } else if let Option::Some(command) = arguments.command {
+        if !checking {
+            for problem in app.problems() {
+                eprintln!("warning: {}", problem);
+            }
+        }
+        let modifies_data = command.modifies_data();
+        app.execute(command)?;
+        if modifies_data {
+            app.save_model()
+        } else {
+            Result::Ok(())
+        }
     } else {
         Result::Err(Error{message:"Nothing to do".into()})
     }
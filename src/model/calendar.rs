@@ -0,0 +1,53 @@
+use super::*;
+
+/*
+ * Business day calendar, saturdays, sundays and the configured holidays are not business days
+ */
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Calendar {
+    pub holidays: Vec<DateData>,
+}
+
+impl Calendar {
+    // one date per line, anything after the date is a description, lines starting with # are comments
+    pub fn parse(data: &str) -> Result<Calendar, ParsingError> {
+        let mut holidays = Vec::new();
+        for (i, line) in data.lines().map(str::trim).enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let date_str = line.split_whitespace().next().unwrap_or(line);
+            let holiday = DateData::parse(date_str).map_err(|error| error.at_line(i + 1).at_column(1, date_str))?;
+            holidays.push(holiday);
+        }
+        Result::Ok(Calendar { holidays })
+    }
+
+    pub fn is_business_day(&self, date: &DateData) -> bool {
+        let weekday = date.weekday();
+        weekday != Weekday::Sat && weekday != Weekday::Sun && !self.holidays.contains(date)
+    }
+
+    pub fn add_business_days(&self, date: &DateData, days: u16) -> Result<DateData, ParsingError> {
+        let mut result = date.clone();
+        let mut remaining = days;
+        while remaining > 0 {
+            result = result.add_days(1)?;
+            if self.is_business_day(&result) {
+                remaining -= 1;
+            }
+        }
+        Result::Ok(result)
+    }
+
+    // fails when the result is after the year 9999
+    pub fn add(&self, date: &DateData, count: u16, unit: &RecurrenceTimeUnit) -> Result<DateData, ParsingError> {
+        match unit {
+            RecurrenceTimeUnit::B => self.add_business_days(date, count),
+            RecurrenceTimeUnit::D => date.add_days(count.into()),
+            RecurrenceTimeUnit::M => date.add_months(count.into()),
+            RecurrenceTimeUnit::W => date.add_weeks(count.into()),
+            RecurrenceTimeUnit::Y => date.add_years(count.into()),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use super::*;
+use std::collections::BTreeSet;
+
+// keys with a meaning of their own, offered along with the tags in use
+const KNOWN_KEYS: &[&str] = &["due", "rec", "t", "uuid"];
+
+impl Model {
+    /*
+     * Words that can replace the partial word being typed: projects after +, contexts after @,
+     * relative dates after due: or t:, otherwise the keys of the tags found in todo and done data.
+     */
+    pub fn completions(&self, word: &str) -> Vec<String> {
+        if let Option::Some((key, value)) = word.split_once(':') {
+            if key != "due" && key != "t" {
+                return Vec::new();
+            }
+            return DateData::relative_shortcuts()
+                .iter()
+                .filter(|shortcut| shortcut.starts_with(value))
+                .map(|shortcut| format!("{}:{}", key, shortcut))
+                .collect();
+        }
+        for kind in &[ElementKind::Project, ElementKind::Context] {
+            if let Option::Some(prefix) = word.strip_prefix(kind.prefix()) {
+                return self
+                    .count_elements(*kind, DataScope::All)
+                    .iter()
+                    .filter(|count| count.name.starts_with(prefix))
+                    .map(|count| format!("{}{}", kind.prefix(), count.name))
+                    .collect();
+            }
+        }
+        if word.is_empty() {
+            return Vec::new();
+        }
+        let mut keys: BTreeSet<&str> = KNOWN_KEYS.iter().copied().collect();
+        for entry in self.todo_data.entries.iter().chain(self.done_data.entries.iter()) {
+            keys.extend(entry.parts.iter().filter_map(|part| match part {
+                TodoElement::Tag { key, .. } => Option::Some(key.as_str()),
+                _ => Option::None,
+            }));
+        }
+        keys.iter()
+            .filter(|key| key.starts_with(word))
+            .map(|key| format!("{}:", key))
+            .collect()
+    }
+}
@@ -1,6 +1,8 @@
 use super::*;
+use chrono::Duration;
+use std::convert::TryFrom;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct DateData {
     pub year: u16,
     pub month: u8,
@@ -8,27 +10,123 @@ pub struct DateData {
 }
 
 impl DateData {
+    // fails unless the date exists in the calendar
+    pub fn new(year: u16, month: u8, day: u8) -> Result<DateData, ParsingError> {
+        NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+            .ok_or_else(|| ParsingError::new("invalid calendar date"))
+            .and_then(|date| DateData::from_naive_date(&date))
+    }
+
+    pub fn today() -> DateData {
+        DateData::from_naive_date(&Local::now().date().naive_local()).expect("today is out of the date range")
+    }
+
+    // fails outside the years 0000 to 9999 that can be written as todo.txt dates
+    pub fn from_naive_date(date: &NaiveDate) -> Result<DateData, ParsingError> {
+        match u16::try_from(date.year()) {
+            Result::Ok(year) if year <= 9999 => Result::Ok(DateData {
+                year,
+                month: date.month() as u8,
+                day: date.day() as u8,
+            }),
+            _ => Result::Err(ParsingError::new("date out of range, years go up to 9999")),
+        }
+    }
+
+    // dates built through new or parse are always valid calendar dates
+    pub fn to_naive_date(&self) -> NaiveDate {
+        NaiveDate::from_ymd(self.year.into(), self.month.into(), self.day.into())
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        self.to_naive_date().weekday()
+    }
+
+    pub fn add_days(&self, days: i64) -> Result<DateData, ParsingError> {
+        // no date is more than 10000 years from another, larger counts would overflow the duration
+        Option::Some(days)
+            .filter(|days| days.abs() <= 10000 * 366)
+            .and_then(|days| self.to_naive_date().checked_add_signed(Duration::days(days)))
+            .ok_or_else(|| ParsingError::new("date out of range, years go up to 9999"))
+            .and_then(|date| DateData::from_naive_date(&date))
+    }
+
+    pub fn add_weeks(&self, weeks: i64) -> Result<DateData, ParsingError> {
+        self.add_days(weeks.saturating_mul(7))
+    }
+
+    // the day is clamped to the length of the resulting month, so 01-31 plus one month is 02-28 (or 02-29)
+    pub fn add_months(&self, months: i32) -> Result<DateData, ParsingError> {
+        let total = i32::from(self.year) * 12 + (i32::from(self.month) - 1) + months;
+        let year = total.div_euclid(12);
+        if !(0..=9999).contains(&year) {
+            return Result::Err(ParsingError::new("date out of range, years go up to 9999"));
+        }
+        let month = total.rem_euclid(12) as u32 + 1;
+        let day = u32::from(self.day).min(DateData::days_in_month(year, month));
+        DateData::new(year as u16, month as u8, day as u8)
+    }
+
+    // 02-29 plus one year is 02-28
+    pub fn add_years(&self, years: i32) -> Result<DateData, ParsingError> {
+        self.add_months(years.saturating_mul(12))
+    }
+
+    pub fn days_until(&self, other: &DateData) -> i64 {
+        other
+            .to_naive_date()
+            .signed_duration_since(self.to_naive_date())
+            .num_days()
+    }
+
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        let first_of_next = if month == 12 {
+            NaiveDate::from_ymd(year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd(year, month + 1, 1)
+        };
+        first_of_next.pred().day()
+    }
+
+    fn parse_segment<T: std::str::FromStr>(
+        segment: &str,
+        length: usize,
+        message: &'static str,
+    ) -> Result<T, ParsingError> {
+        if segment.len() != length || !segment.chars().all(|c| c.is_ascii_digit()) {
+            return Result::Err(ParsingError::new(message));
+        }
+        segment.parse::<T>().map_err(|_| ParsingError::new(message))
+    }
+
+    // YYYY-MM-DD
     pub fn parse(date_str: &str) -> Result<DateData, ParsingError> {
         let x: Vec<&str> = date_str.split('-').collect();
-        match (x.get(0), x.get(1), x.get(2)) {
-            (Some(year_str), Some(month_str), Some(day_str)) => Result::Ok(DateData {
-                year: year_str.parse::<u16>().map_err(|_| ParsingError {
-                    message: "error parsing year",
-                })?,
-                month: month_str.parse::<u8>().map_err(|_| ParsingError {
-                    message: "error parsing month",
-                })?,
-                day: day_str.parse::<u8>().map_err(|_| ParsingError {
-                    message: "error parsing day",
-                })?,
-            }),
-            _ => Result::Err(ParsingError {
-                message: "error parsing date",
-            }),
+        match x.as_slice() {
+            [year_str, month_str, day_str] => DateData::new(
+                DateData::parse_segment(year_str, 4, "error parsing year")?,
+                DateData::parse_segment(month_str, 2, "error parsing month")?,
+                DateData::parse_segment(day_str, 2, "error parsing day")?,
+            ),
+            _ => Result::Err(ParsingError::new("error parsing date")),
         }
     }
 }
 
+impl TryFrom<NaiveDate> for DateData {
+    type Error = ParsingError;
+
+    fn try_from(date: NaiveDate) -> Result<Self, Self::Error> {
+        DateData::from_naive_date(&date)
+    }
+}
+
+impl From<&DateData> for NaiveDate {
+    fn from(date: &DateData) -> Self {
+        date.to_naive_date()
+    }
+}
+
 impl fmt::Display for DateData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}-{:02}-{:02}", self.year, self.month, self.day)
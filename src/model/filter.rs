@@ -0,0 +1,300 @@
+use super::*;
+
+/*
+ * Filter expressions for listing entries, for example:
+ *
+ *   +work and (due:<today or pri:A..B) -@phone
+ *
+ * Adjacent terms are joined with an implicit `and`, `-` negates a single term.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub enum Filter {
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Project(String),
+    Context(String),
+    Date(DateField, Comparison, DateValue),
+    Priority(Priority, Priority),
+    Status(StatusFilter),
+    Tag(String, Comparison, String),
+    Text(String),
+    // letters of the text in order, not necessarily next to each other
+    Fuzzy(String),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DateField {
+    Created,
+    Done,
+    Due,
+    Threshold,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Comparison {
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DateValue {
+    Date(DateData),
+    Today,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StatusFilter {
+    Done,
+    Open,
+}
+
+impl DateField {
+    fn parse(key: &str) -> Option<DateField> {
+        match key {
+            "created" => Option::Some(DateField::Created),
+            "done" => Option::Some(DateField::Done),
+            "due" => Option::Some(DateField::Due),
+            "t" => Option::Some(DateField::Threshold),
+            _ => Option::None,
+        }
+    }
+
+    pub fn value<'a>(&self, entry: &'a TodoEntry) -> Option<&'a DateData> {
+        match self {
+            DateField::Created => entry.created_date.as_ref(),
+            DateField::Done => match &entry.status {
+                Status::Done(date) => date.as_ref(),
+                Status::Open => Option::None,
+            },
+            DateField::Due => entry.parts.iter().find_map(|part| match part {
+                TodoElement::Due(date) => Option::Some(date),
+                _ => Option::None,
+            }),
+            DateField::Threshold => entry.parts.iter().find_map(|part| match part {
+                TodoElement::Threshold(date) => Option::Some(date),
+                _ => Option::None,
+            }),
+        }
+    }
+}
+
+impl Comparison {
+    fn split(input: &str) -> (Comparison, &str) {
+        for (prefix, comparison) in &[
+            (">=", Comparison::GreaterOrEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+            ("=", Comparison::Equal),
+        ] {
+            if let Option::Some(rest) = input.strip_prefix(prefix) {
+                return (*comparison, rest);
+            }
+        }
+        (Comparison::Equal, input)
+    }
+
+    fn holds(&self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering;
+        match self {
+            Comparison::Equal => ordering == Ordering::Equal,
+            Comparison::Greater => ordering == Ordering::Greater,
+            Comparison::GreaterOrEqual => ordering != Ordering::Less,
+            Comparison::Less => ordering == Ordering::Less,
+            Comparison::LessOrEqual => ordering != Ordering::Greater,
+        }
+    }
+}
+
+impl DateValue {
+    fn parse(input: &str) -> Result<DateValue, ParsingError> {
+        match input {
+            "today" => Result::Ok(DateValue::Today),
+            _ => DateData::parse(input).map(DateValue::Date),
+        }
+    }
+
+    fn resolve(&self, today: &DateData) -> DateData {
+        match self {
+            DateValue::Date(date) => date.clone(),
+            DateValue::Today => today.clone(),
+        }
+    }
+}
+
+impl Filter {
+    pub fn parse(input: &str) -> Result<Filter, ParsingError> {
+        let tokens = Filter::tokenize(input);
+        let mut position = 0;
+        let filter = Filter::parse_or(&tokens, &mut position)?;
+        if position < tokens.len() {
+            return Result::Err(ParsingError::new("unexpected token in filter"));
+        }
+        Result::Ok(filter)
+    }
+
+    fn tokenize(input: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut current = String::new();
+        for c in input.chars() {
+            if c.is_whitespace() || c == '(' || c == ')' {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                if !c.is_whitespace() {
+                    tokens.push(c.to_string());
+                }
+            } else {
+                current.push(c);
+            }
+        }
+        if !current.is_empty() {
+            tokens.push(current);
+        }
+        tokens
+    }
+
+    fn is_keyword(token: &str, keyword: &str) -> bool {
+        token.eq_ignore_ascii_case(keyword)
+    }
+
+    fn parse_or(tokens: &[String], position: &mut usize) -> Result<Filter, ParsingError> {
+        let mut left = Filter::parse_and(tokens, position)?;
+        while let Option::Some(token) = tokens.get(*position) {
+            if !Filter::is_keyword(token, "or") {
+                break;
+            }
+            *position += 1;
+            let right = Filter::parse_and(tokens, position)?;
+            left = Filter::Or(Box::new(left), Box::new(right));
+        }
+        Result::Ok(left)
+    }
+
+    fn parse_and(tokens: &[String], position: &mut usize) -> Result<Filter, ParsingError> {
+        let mut left = Filter::parse_not(tokens, position)?;
+        while let Option::Some(token) = tokens.get(*position) {
+            if Filter::is_keyword(token, "or") || token == ")" {
+                break;
+            }
+            if Filter::is_keyword(token, "and") {
+                *position += 1;
+            }
+            let right = Filter::parse_not(tokens, position)?;
+            left = Filter::And(Box::new(left), Box::new(right));
+        }
+        Result::Ok(left)
+    }
+
+    fn parse_not(tokens: &[String], position: &mut usize) -> Result<Filter, ParsingError> {
+        match tokens.get(*position) {
+            Option::Some(token) if Filter::is_keyword(token, "not") => {
+                *position += 1;
+                Filter::parse_not(tokens, position).map(|filter| Filter::Not(Box::new(filter)))
+            }
+            Option::Some(token) if token == "(" => {
+                *position += 1;
+                let filter = Filter::parse_or(tokens, position)?;
+                match tokens.get(*position) {
+                    Option::Some(token) if token == ")" => {
+                        *position += 1;
+                        Result::Ok(filter)
+                    }
+                    _ => Result::Err(ParsingError::new("missing closing parenthesis in filter")),
+                }
+            }
+            Option::Some(token)
+                if token == ")" || Filter::is_keyword(token, "and") || Filter::is_keyword(token, "or") =>
+            {
+                Result::Err(ParsingError::new("unexpected token in filter"))
+            }
+            Option::Some(token) => {
+                *position += 1;
+                match token.strip_prefix('-') {
+                    Option::Some(negated) if !negated.is_empty() => {
+                        Filter::parse_term(negated).map(|filter| Filter::Not(Box::new(filter)))
+                    }
+                    _ => Filter::parse_term(token),
+                }
+            }
+            Option::None => Result::Err(ParsingError::new("unexpected end of filter")),
+        }
+    }
+
+    fn parse_term(token: &str) -> Result<Filter, ParsingError> {
+        if let Option::Some(project) = token.strip_prefix('+') {
+            return Result::Ok(Filter::Project(project.into()));
+        }
+        if let Option::Some(context) = token.strip_prefix('@') {
+            return Result::Ok(Filter::Context(context.into()));
+        }
+        if let Option::Some((key, value)) = token.split_once(':') {
+            if let Option::Some(field) = DateField::parse(key) {
+                let (comparison, date_str) = Comparison::split(value);
+                return DateValue::parse(date_str).map(|date| Filter::Date(field, comparison, date));
+            }
+            if key == "pri" {
+                let (from, to) = value.split_once("..").unwrap_or((value, value));
+                let (from, to) = (Priority::from_letter(from)?, Priority::from_letter(to)?);
+                return Result::Ok(Filter::Priority(from.min(to), from.max(to)));
+            }
+            if key == "status" {
+                return match value {
+                    "done" => Result::Ok(Filter::Status(StatusFilter::Done)),
+                    "open" => Result::Ok(Filter::Status(StatusFilter::Open)),
+                    _ => Result::Err(ParsingError::new("error parsing status filter")),
+                };
+            }
+            if let TodoElement::Tag { key, .. } = TodoElement::parse(token) {
+                let (comparison, value) = Comparison::split(value);
+                return Result::Ok(Filter::Tag(key, comparison, value.into()));
+            }
+        }
+        Result::Ok(Filter::Text(token.to_lowercase()))
+    }
+
+    // the same filter with its text terms matched fuzzily
+    pub fn fuzzy(self) -> Filter {
+        match self {
+            Filter::And(left, right) => Filter::And(Box::new(left.fuzzy()), Box::new(right.fuzzy())),
+            Filter::Or(left, right) => Filter::Or(Box::new(left.fuzzy()), Box::new(right.fuzzy())),
+            Filter::Not(filter) => Filter::Not(Box::new(filter.fuzzy())),
+            Filter::Text(text) => Filter::Fuzzy(text),
+            filter => filter,
+        }
+    }
+
+    pub fn matches(&self, entry: &TodoEntry, today: &DateData) -> bool {
+        match self {
+            Filter::And(left, right) => left.matches(entry, today) && right.matches(entry, today),
+            Filter::Or(left, right) => left.matches(entry, today) || right.matches(entry, today),
+            Filter::Not(filter) => !filter.matches(entry, today),
+            Filter::Project(project) => entry.parts.contains(&TodoElement::project(project)),
+            Filter::Context(context) => entry.parts.contains(&TodoElement::context(context)),
+            Filter::Date(field, comparison, value) => field
+                .value(entry)
+                .map(|date| comparison.holds(date.cmp(&value.resolve(today))))
+                .unwrap_or(false),
+            Filter::Priority(from, to) => entry
+                .priority
+                .map(|priority| *from <= priority && priority <= *to)
+                .unwrap_or(false),
+            Filter::Status(StatusFilter::Done) => matches!(entry.status, Status::Done(_)),
+            Filter::Status(StatusFilter::Open) => entry.status == Status::Open,
+            Filter::Tag(key, comparison, value) => entry
+                .tag(key)
+                .map(|tag_value| comparison.holds(TodoElement::compare_tag_values(tag_value, value)))
+                .unwrap_or(false),
+            Filter::Text(text) => format!("{}", entry).to_lowercase().contains(text),
+            Filter::Fuzzy(text) => {
+                let entry_text = format!("{}", entry).to_lowercase();
+                let mut chars = entry_text.chars();
+                text.chars().all(|letter| chars.any(|c| c == letter))
+            }
+        }
+    }
+}
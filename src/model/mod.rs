@@ -1,63 +1,372 @@
-mod tests;
+pub(crate) mod tests;
 mod todoentry;
 mod todoelement;
 mod datedata;
+mod relativedate;
 mod status;
+mod priority;
+mod render;
+mod calendar;
+mod completion;
+mod settings;
+mod filter;
+mod sort;
+mod selector;
+mod summary;
 mod tododata;
 
 use chrono::prelude::*;
 use common::*;
 use std::fmt;
 use crate::args::Command;
+use crate::args::DataScope;
+use crate::args::FutureEntries;
+use crate::args::ListOptions;
 
 pub use todoentry::*;
 pub use todoelement::*;
 pub use datedata::*;
 pub use status::*;
+pub use priority::*;
+pub use render::*;
+pub use calendar::*;
+pub use settings::*;
+pub use filter::*;
+pub use sort::*;
+pub use selector::*;
+pub use summary::*;
 pub use tododata::*;
 
 #[derive(Debug, PartialEq)]
 pub struct Model {
     pub todo_data: TodoData,
     pub done_data: TodoData,
+    pub settings: Settings,
 }
 
 impl Model {
+    pub fn today(&self) -> DateData {
+        match &self.settings.today {
+            Option::Some(today) => today.clone(),
+            Option::None => DateData::today(),
+        }
+    }
+
     pub fn execute(self: &mut Model, command: crate::args::Command) -> Result<(), &str> {
+        let today = self.today();
         match command {
-            Command::Add(text) => TodoEntry::parse(&text)
-                .and_then(|e| Result::Ok(self.todo_data.entries.push(e)))
-                .map_err(|e| e.message),
-            Command::Archive(offset) => Result::Ok({
-                self.done_data
-                    .entries
-                    .push(self.todo_data.entries.remove(offset.into()))
-            }),
-            Command::Do(index) => Result::Ok({
-                let date = Local::now().date();
-                let year = date.year() as u16;
-                let month = date.month() as u8;
-                let day = date.day() as u8;
-                self.todo_data.entries[index as usize].status =
-                    Status::Done(Option::Some(DateData { year, month, day }))
-            }),
-            Command::List => Result::Ok(
-                for (i, entry) in self.todo_data.entries.iter().enumerate() {
-                    println!("[{}] {}", i, entry)
-                },
-            ),
-            Command::Remove(index) => { 
-                self.todo_data.entries.remove(index as usize);
+            Command::Add(text) => {
+                // spaces around the text, often left by completion, are not kept
+                let text = TodoEntry::expand_relative_dates(text.trim(), &today, &self.settings.calendar);
+                let mut entry = TodoEntry::parse(&text).map_err(|e| e.message)?;
+                if self.settings.date_on_add && entry.created_date.is_none() {
+                    entry.created_date = Option::Some(today.clone());
+                }
+                if self.settings.auto_uuid && entry.uuid().is_none() {
+                    entry.parts.push(TodoElement::Uuid(Uuid::new_v4()));
+                }
+                self.todo_data.entries.push(entry);
+                Result::Ok(())
+            }
+            Command::AddPrompt => Result::Err("add without text is not a model command"),
+            Command::Append(selector, text) => {
+                let parts = self.parse_parts(&text, &today)?;
+                for index in selector.resolve(&self.todo_data, &today)? {
+                    let entry = &mut self.todo_data.entries[index];
+                    for part in parts.iter().cloned() {
+                        TodoEntry::push(&mut entry.parts, part);
+                    }
+                }
+                Result::Ok(())
+            }
+            Command::Archive(Option::Some(selector)) => {
+                let indexes = selector.resolve(&self.todo_data, &today)?;
+                let archived = self.take_entries(&indexes);
+                self.done_data.entries.extend(archived);
+                Result::Ok(())
+            }
+            Command::Archive(Option::None) => {
+                let count = self.archive_done();
+                println!("{} task(s) archived", count);
+                Result::Ok(())
+            }
+            Command::Check => Result::Err("check is not a model command"),
+            Command::Complete(_) | Command::Completions(_) => Result::Err("completion is not a model command"),
+            Command::Contexts(scope) => {
+                for count in self.count_elements(ElementKind::Context, scope) {
+                    println!("{}", count);
+                }
+                Result::Ok(())
+            }
+            Command::Depri(selector) => {
+                for index in selector.resolve(&self.todo_data, &today)? {
+                    self.todo_data.entries[index].priority = Option::None;
+                }
+                Result::Ok(())
+            }
+            // entries that are already done keep their completion date
+            Command::Do(selector) => {
+                let indexes: Vec<usize> = selector
+                    .resolve(&self.todo_data, &today)?
+                    .into_iter()
+                    .filter(|index| self.todo_data.entries[*index].status == Status::Open)
+                    .collect();
+                // next occurrences come first so a date out of range leaves every task as it was
+                let mut next_entries = Vec::new();
+                for index in &indexes {
+                    let entry = &self.todo_data.entries[*index];
+                    if let Option::Some(mut next) =
+                        entry.next_occurrence(&today, &self.settings.calendar).map_err(|e| e.message)?
+                    {
+                        if self.settings.auto_uuid {
+                            next.parts.push(TodoElement::Uuid(Uuid::new_v4()));
+                        }
+                        next_entries.push(next);
+                    }
+                }
+                for index in indexes {
+                    self.todo_data.entries[index].complete(today.clone());
+                }
+                self.todo_data.entries.extend(next_entries);
+                Result::Ok(())
+            }
+            Command::Edit(_) => Result::Err("edit is not a model command"),
+            Command::List(options) => {
+                let renderer = Renderer::for_stdout(&self.settings.colors);
+                for (i, entry) in self.list(&options, &today) {
+                    println!("{}", renderer.render(i, entry, &today))
+                }
+                Result::Ok(())
+            }
+            Command::Prepend(selector, text) => {
+                let parts = self.parse_parts(&text, &today)?;
+                for index in selector.resolve(&self.todo_data, &today)? {
+                    let entry = &mut self.todo_data.entries[index];
+                    let mut prepended = parts.clone();
+                    for part in entry.parts.drain(..) {
+                        TodoEntry::push(&mut prepended, part);
+                    }
+                    entry.parts = prepended;
+                }
+                Result::Ok(())
+            }
+            Command::Pri(selector, priority) => {
+                for index in selector.resolve(&self.todo_data, &today)? {
+                    self.todo_data.entries[index].priority = Option::Some(priority);
+                }
+                Result::Ok(())
+            }
+            Command::Projects(scope) => {
+                for count in self.count_elements(ElementKind::Project, scope) {
+                    println!("{}", count);
+                }
                 Result::Ok(())
-            },
-            Command::Undo(index) => Result::Ok( self.todo_data.entries[index as usize].status = Status::Open ),
+            }
+            Command::Remove(selector) => {
+                let indexes = selector.resolve(&self.todo_data, &today)?;
+                self.take_entries(&indexes);
+                Result::Ok(())
+            }
+            Command::Replace(selector, text) => {
+                let index = self.select_one(&selector)?;
+                self.replace(index, &text)
+            }
+            Command::Rename(options) => {
+                let changes = self.rename_element(options.kind, &options.old, &options.new, options.dry_run);
+                if options.dry_run {
+                    for (before, after) in &changes {
+                        println!("- {}\n+ {}", before, after);
+                    }
+                } else {
+                    println!("{} task(s) changed", changes.len());
+                }
+                Result::Ok(())
+            }
+            Command::Ui => Result::Err("ui is not a model command"),
+            Command::Undo(selector) => {
+                for index in selector.resolve(&self.todo_data, &today)? {
+                    let entry = &mut self.todo_data.entries[index];
+                    if let Status::Done(_) = entry.status {
+                        entry.reopen();
+                    }
+                }
+                Result::Ok(())
+            }
+        }
+    }
+}
+
+impl Model {
+    /// Entries selected by the list options paired with their index in the todo data
+    pub fn list(&self, options: &ListOptions, today: &DateData) -> Vec<(usize, &TodoEntry)> {
+        let mut entries: Vec<(usize, &TodoEntry)> = self
+            .todo_data
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| match options.future {
+                FutureEntries::Hide => !Model::is_future(entry, today),
+                // beyond the last date nothing is in the future
+                FutureEntries::IncludeDays(days) => match today.add_days(days.into()) {
+                    Result::Ok(limit) => !Model::is_future(entry, &limit),
+                    Result::Err(_) => true,
+                },
+                FutureEntries::Show => true,
+            })
+            .filter(|(_, entry)| match &options.filter {
+                Option::Some(filter) => filter.matches(entry, today),
+                Option::None => true,
+            })
+            .collect();
+        entries.sort_by(|(_, a), (_, b)| SortKey::compare_all(&options.sort, a, b));
+        entries
+    }
+
+    pub fn select_one(&self, selector: &Selector) -> Result<usize, &'static str> {
+        match selector.resolve(&self.todo_data, &self.today())?.as_slice() {
+            [index] => Result::Ok(*index),
+            _ => Result::Err("the selector must match exactly one task"),
+        }
+    }
+
+    /// Replaces the text of an entry. Status, priority and created date are kept unless the new
+    /// text brings its own.
+    pub fn replace(&mut self, index: usize, text: &str) -> Result<(), &'static str> {
+        let text = TodoEntry::expand_relative_dates(text, &self.today(), &self.settings.calendar);
+        let replacement = TodoEntry::parse(&text).map_err(|e| e.message)?;
+        let entry = &mut self.todo_data.entries[index];
+        if let Status::Done(_) = replacement.status {
+            entry.status = replacement.status;
+        }
+        entry.priority = replacement.priority.or(entry.priority);
+        entry.created_date = replacement.created_date.or_else(|| entry.created_date.take());
+        entry.parts = replacement.parts;
+        Result::Ok(())
+    }
+
+    /// Replaces an entry with an edited line. Unlike replace the whole line decides, so removing
+    /// the leading x reopens a done task and brings back its priority.
+    pub fn edit(&mut self, index: usize, line: &str) -> Result<(), &'static str> {
+        let line = TodoEntry::expand_relative_dates(line, &self.today(), &self.settings.calendar);
+        let mut edited = TodoEntry::parse(&line).map_err(|e| e.message)?;
+        let entry = &mut self.todo_data.entries[index];
+        if let (Status::Done(_), Status::Open) = (&entry.status, &edited.status) {
+            edited.reopen();
+        }
+        *entry = edited;
+        Result::Ok(())
+    }
+
+    // elements of text added to an existing entry, no status, priority or created date
+    fn parse_parts(&self, text: &str, today: &DateData) -> Result<Vec<TodoElement>, &'static str> {
+        let text = TodoEntry::expand_relative_dates(text, today, &self.settings.calendar);
+        let mut parts = Vec::new();
+        for token in text.split_whitespace() {
+            TodoEntry::push(&mut parts, TodoElement::parse_strict(token).map_err(|e| e.message)?);
         }
+        Result::Ok(parts)
+    }
+
+    // removes the entries at the given ascending indexes, returns them in the same order
+    fn take_entries(&mut self, indexes: &[usize]) -> Vec<TodoEntry> {
+        let mut taken: Vec<TodoEntry> = indexes
+            .iter()
+            .rev()
+            .map(|index| self.todo_data.entries.remove(*index))
+            .collect();
+        taken.reverse();
+        taken
+    }
+
+    fn is_future(entry: &TodoEntry, today: &DateData) -> bool {
+        match DateField::Threshold.value(entry) {
+            Option::Some(threshold) => threshold > today,
+            Option::None => false,
+        }
+    }
+
+    /// Moves every done entry to the done data keeping their relative order,
+    /// returns the number of entries moved
+    pub fn archive_done(&mut self) -> usize {
+        let (done, open): (Vec<TodoEntry>, Vec<TodoEntry>) = self
+            .todo_data
+            .entries
+            .drain(..)
+            .partition(|entry| matches!(entry.status, Status::Done(_)));
+        self.todo_data.entries = open;
+        let count = done.len();
+        self.done_data.entries.extend(done);
+        count
     }
 }
 
 pub mod common {
-    #[derive(Debug)]
+    use std::fmt;
+
+    #[derive(Debug, PartialEq, Clone)]
     pub struct ParsingError {
         pub message: &'static str,
+        pub file: Option<String>,
+        // line and column start at 1
+        pub line: Option<usize>,
+        pub column: Option<usize>,
+        pub token: Option<String>,
+    }
+
+    impl ParsingError {
+        pub fn new(message: &'static str) -> ParsingError {
+            ParsingError {
+                message,
+                file: Option::None,
+                line: Option::None,
+                column: Option::None,
+                token: Option::None,
+            }
+        }
+
+        pub fn at_column(self, column: usize, token: &str) -> ParsingError {
+            ParsingError {
+                column: Option::Some(column),
+                token: Option::Some(token.into()),
+                ..self
+            }
+        }
+
+        pub fn at_line(self, line: usize) -> ParsingError {
+            ParsingError {
+                line: Option::Some(line),
+                ..self
+            }
+        }
+
+        pub fn in_file(self, file: &str) -> ParsingError {
+            ParsingError {
+                file: Option::Some(file.into()),
+                ..self
+            }
+        }
+    }
+
+    // file:line:column: message `token`, like compiler diagnostics
+    impl fmt::Display for ParsingError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if let Option::Some(file) = &self.file {
+                write!(f, "{}:", file)?;
+            }
+            if let Option::Some(line) = self.line {
+                write!(f, "{}:", line)?;
+            }
+            if let Option::Some(column) = self.column {
+                write!(f, "{}:", column)?;
+            }
+            if self.file.is_some() || self.line.is_some() || self.column.is_some() {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", self.message)?;
+            if let Option::Some(token) = &self.token {
+                write!(f, " `{}`", token)?;
+            }
+            Result::Ok(())
+        }
     }
 }
@@ -0,0 +1,38 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Priority {
+    pub letter: char,
+}
+
+impl Priority {
+    pub fn new(letter: char) -> Result<Priority, ParsingError> {
+        if letter.is_ascii_uppercase() {
+            Result::Ok(Priority { letter })
+        } else {
+            Result::Err(ParsingError::new("error parsing priority"))
+        }
+    }
+
+    pub fn from_letter(letter_str: &str) -> Result<Priority, ParsingError> {
+        let mut chars = letter_str.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) => Priority::new(letter.to_ascii_uppercase()),
+            _ => Result::Err(ParsingError::new("error parsing priority")),
+        }
+    }
+
+    pub fn parse(priority_str: &str) -> Result<Priority, ParsingError> {
+        let mut chars = priority_str.chars();
+        match (chars.next(), chars.next(), chars.next(), chars.next()) {
+            (Some('('), Some(letter), Some(')'), None) => Priority::new(letter),
+            _ => Result::Err(ParsingError::new("error parsing priority")),
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({})", self.letter)
+    }
+}
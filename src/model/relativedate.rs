@@ -0,0 +1,77 @@
+use super::*;
+
+const WEEKDAYS: &[(&str, &str, Weekday)] = &[
+    ("mon", "monday", Weekday::Mon),
+    ("tue", "tuesday", Weekday::Tue),
+    ("wed", "wednesday", Weekday::Wed),
+    ("thu", "thursday", Weekday::Thu),
+    ("fri", "friday", Weekday::Fri),
+    ("sat", "saturday", Weekday::Sat),
+    ("sun", "sunday", Weekday::Sun),
+];
+
+impl DateData {
+    /*
+     * Absolute dates or expressions relative to today: today, tomorrow, weekday names (the next one
+     * after today), +Nd, +Nb, +Nw, +Nm, +Ny, eom (end of this month) and next-month (its first day)
+     */
+    pub fn parse_relative(
+        input: &str,
+        today: &DateData,
+        calendar: &Calendar,
+    ) -> Result<DateData, ParsingError> {
+        let lowercase = input.to_lowercase();
+        match lowercase.as_str() {
+            "today" => return Result::Ok(today.clone()),
+            "tomorrow" => return today.add_days(1),
+            "eom" => return today.add_days(1 - i64::from(today.day))?.add_months(1)?.add_days(-1),
+            "next-month" => return today.add_days(1 - i64::from(today.day))?.add_months(1),
+            _ => (),
+        }
+        if let Option::Some((_, _, weekday)) = WEEKDAYS
+            .iter()
+            .find(|(short, long, _)| lowercase == *short || lowercase == *long)
+        {
+            let from_monday = today.weekday().num_days_from_monday();
+            let days_ahead = (weekday.num_days_from_monday() + 6 - from_monday) % 7 + 1;
+            return today.add_days(days_ahead.into());
+        }
+        if let Option::Some(offset) = lowercase.strip_prefix('+') {
+            if let TodoElement::Recurrence { count, unit, .. } = TodoElement::parse(&format!("rec:{}", offset)) {
+                return calendar.add(today, count, &unit);
+            }
+        }
+        DateData::parse(input)
+    }
+
+    // expressions offered when completing due: and t: values
+    pub fn relative_shortcuts() -> Vec<&'static str> {
+        let mut shortcuts = vec!["today", "tomorrow", "eom", "next-month", "+1d", "+1b", "+1w", "+1m", "+1y"];
+        shortcuts.extend(WEEKDAYS.iter().map(|(_, long, _)| *long));
+        shortcuts
+    }
+}
+
+impl TodoEntry {
+    // replaces relative due: and t: values with absolute dates, anything that is not a date is left alone
+    pub fn expand_relative_dates(text: &str, today: &DateData, calendar: &Calendar) -> String {
+        let mut result = String::new();
+        let mut rest = text;
+        while let Option::Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let token = &rest[..end];
+            let expanded = ["due:", "t:"].iter().find_map(|prefix| {
+                token
+                    .strip_prefix(prefix)
+                    .and_then(|value| DateData::parse_relative(value, today, calendar).ok())
+                    .map(|date| format!("{}{}", prefix, date))
+            });
+            result.push_str(expanded.as_deref().unwrap_or(token));
+            rest = &rest[end..];
+        }
+        result.push_str(rest);
+        result
+    }
+}
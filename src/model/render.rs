@@ -0,0 +1,159 @@
+use super::*;
+use crossterm::style::{Color, Stylize};
+use std::convert::TryFrom;
+use std::io::IsTerminal;
+
+/*
+ * Colors of the list output. Each one can be changed in todocommander.cfg with a line such as
+ *
+ *   color_priority=dark_yellow
+ *
+ * using the color names of crossterm (red, dark_cyan, grey...) or none.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Colors {
+    pub context: Option<Color>,
+    pub done: Option<Color>,
+    pub overdue: Option<Color>,
+    pub priority: Option<Color>,
+    pub project: Option<Color>,
+}
+
+impl Default for Colors {
+    fn default() -> Colors {
+        Colors {
+            context: Option::Some(Color::Cyan),
+            done: Option::Some(Color::DarkGrey),
+            overdue: Option::Some(Color::Red),
+            priority: Option::Some(Color::Yellow),
+            project: Option::Some(Color::Magenta),
+        }
+    }
+}
+
+impl Colors {
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), ParsingError> {
+        let color = match value {
+            "none" => Option::None,
+            _ => Option::Some(Color::try_from(value).map_err(|_| ParsingError::new("unknown color"))?),
+        };
+        let slot = match name {
+            "context" => &mut self.context,
+            "done" => &mut self.done,
+            "overdue" => &mut self.overdue,
+            "priority" => &mut self.priority,
+            "project" => &mut self.project,
+            _ => return Result::Err(ParsingError::new("unknown color setting")),
+        };
+        *slot = color;
+        Result::Ok(())
+    }
+}
+
+pub struct Renderer {
+    colors: Colors,
+    color: bool,
+    // columns available, no wrapping when unknown
+    width: Option<usize>,
+}
+
+impl Renderer {
+    pub fn new(colors: Colors, color: bool, width: Option<usize>) -> Renderer {
+        Renderer { colors, color, width }
+    }
+
+    // colors only on a terminal unless NO_COLOR is set, wraps to COLUMNS or else the terminal width
+    pub fn for_stdout(colors: &Colors) -> Renderer {
+        let terminal = std::io::stdout().is_terminal();
+        let width = match std::env::var("COLUMNS").ok().and_then(|columns| columns.parse::<usize>().ok()) {
+            Option::Some(columns) => Option::Some(columns),
+            Option::None if terminal => crossterm::terminal::size().ok().map(|(columns, _)| columns.into()),
+            Option::None => Option::None,
+        };
+        Renderer::new(colors.clone(), terminal && !Renderer::no_color(), width)
+    }
+
+    // one line per entry for the full screen ui, which cuts lines at the screen edge
+    pub fn for_screen(colors: &Colors) -> Renderer {
+        Renderer::new(colors.clone(), !Renderer::no_color(), Option::None)
+    }
+
+    // https://no-color.org
+    fn no_color() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+    }
+
+    /*
+     * The entry prefixed by its index. Without colors nor width the entry is written as stored,
+     * otherwise words are colored and lines wrapped under the start of the entry.
+     */
+    pub fn render(&self, index: usize, entry: &TodoEntry, today: &DateData) -> String {
+        let prefix = format!("[{}] ", index);
+        if !self.color && self.width.is_none() {
+            return format!("{}{}", prefix, entry);
+        }
+        let indent = prefix.chars().count();
+        let mut output = prefix;
+        let mut column = indent;
+        for (i, (word, color)) in self.words(entry, today).iter().enumerate() {
+            let length = word.chars().count();
+            if i > 0 {
+                match self.width {
+                    Option::Some(width) if column + 1 + length > width => {
+                        output.push('\n');
+                        output.push_str(&" ".repeat(indent));
+                        column = indent;
+                    }
+                    _ => {
+                        output.push(' ');
+                        column += 1;
+                    }
+                }
+            }
+            output.push_str(&self.paint(word, *color));
+            column += length;
+        }
+        output
+    }
+
+    fn words(&self, entry: &TodoEntry, today: &DateData) -> Vec<(String, Option<Color>)> {
+        let done = matches!(entry.status, Status::Done(_));
+        let mut words: Vec<(String, Option<Color>)> = Vec::new();
+        if let Status::Done(date) = &entry.status {
+            words.push(("x".into(), Option::None));
+            if let Option::Some(date) = date {
+                words.push((format!("{}", date), Option::None));
+            }
+        }
+        if let Option::Some(priority) = &entry.priority {
+            words.push((format!("{}", priority), self.colors.priority));
+        }
+        if let Option::Some(date) = &entry.created_date {
+            words.push((format!("{}", date), Option::None));
+        }
+        for part in &entry.parts {
+            match part {
+                TodoElement::Text(text) => words.extend(text.split_whitespace().map(|word| (word.into(), Option::None))),
+                TodoElement::Project(_) => words.push((format!("{}", part), self.colors.project)),
+                TodoElement::Context(_) => words.push((format!("{}", part), self.colors.context)),
+                TodoElement::Due(date) if !done && date < today => {
+                    words.push((format!("{}", part), self.colors.overdue))
+                }
+                _ => words.push((format!("{}", part), Option::None)),
+            }
+        }
+        if done {
+            for (_, color) in words.iter_mut() {
+                *color = self.colors.done;
+            }
+        }
+        words
+    }
+
+    fn paint(&self, word: &str, color: Option<Color>) -> String {
+        match (self.color, color) {
+            (true, Option::Some(color)) => format!("{}", word.with(color)),
+            _ => word.into(),
+        }
+    }
+}
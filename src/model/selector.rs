@@ -0,0 +1,103 @@
+use super::*;
+
+/*
+ * Selects the entries a command applies to: an index (3), a list of indexes and ranges (1,4,7 or 2-9),
+ * a uuid (uuid:67e55044-10b1-426f-9247-bb680e5fe0c8) or a list filter made of +project, @context
+ * and key:value terms (+work). Filters with free text must be marked with a leading slash
+ * (/call) so a mistyped index cannot match tasks by their text.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub enum Selector {
+    Indexes(Vec<u16>),
+    Uuid(Uuid),
+    Filter(Filter),
+}
+
+impl From<u16> for Selector {
+    fn from(index: u16) -> Self {
+        Selector::Indexes(vec![index])
+    }
+}
+
+impl Selector {
+    pub fn parse(input: &str) -> Result<Selector, ParsingError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Result::Err(ParsingError::new("missing task selector"));
+        }
+        if let Option::Some(indexes) = Selector::parse_indexes(input) {
+            return indexes.map(Selector::Indexes);
+        }
+        if let Option::Some(uuid_str) = input.strip_prefix("uuid:") {
+            return Uuid::parse(uuid_str).map(Selector::Uuid);
+        }
+        if let Option::Some(filter) = input.strip_prefix('/') {
+            return Filter::parse(filter).map(Selector::Filter);
+        }
+        let filter = Filter::parse(input)?;
+        if Selector::has_text(&filter) {
+            return Result::Err(ParsingError::new(
+                "error parsing task selector, start a filter with free text with /",
+            ));
+        }
+        Result::Ok(Selector::Filter(filter))
+    }
+
+    fn has_text(filter: &Filter) -> bool {
+        match filter {
+            Filter::And(left, right) | Filter::Or(left, right) => Selector::has_text(left) || Selector::has_text(right),
+            Filter::Not(filter) => Selector::has_text(filter),
+            Filter::Text(_) | Filter::Fuzzy(_) => true,
+            _ => false,
+        }
+    }
+
+    // None when the input does not look like a list of indexes at all
+    fn parse_indexes(input: &str) -> Option<Result<Vec<u16>, ParsingError>> {
+        if !input.chars().all(|c| c.is_ascii_digit() || c == ',' || c == '-') {
+            return Option::None;
+        }
+        let error = ParsingError::new("error parsing task index");
+        let mut indexes = Vec::new();
+        for item in input.split(',') {
+            let bounds: Result<Vec<u16>, _> = item.splitn(2, '-').map(str::parse::<u16>).collect();
+            match bounds.as_deref() {
+                Result::Ok([index]) => indexes.push(*index),
+                Result::Ok([from, to]) if from <= to => indexes.extend(*from..=*to),
+                _ => return Option::Some(Result::Err(error)),
+            }
+        }
+        Option::Some(Result::Ok(indexes))
+    }
+
+    // indexes of the selected entries in ascending order, fails unless every index exists and something matches
+    pub fn resolve(&self, data: &TodoData, today: &DateData) -> Result<Vec<usize>, &'static str> {
+        let mut indexes: Vec<usize> = match self {
+            Selector::Indexes(indexes) => {
+                let indexes: Vec<usize> = indexes.iter().map(|index| *index as usize).collect();
+                if indexes.iter().any(|index| *index >= data.entries.len()) {
+                    return Result::Err("task index out of range");
+                }
+                indexes
+            }
+            Selector::Uuid(uuid) => Selector::positions(data, |entry| entry.uuid() == Option::Some(uuid)),
+            Selector::Filter(filter) => Selector::positions(data, |entry| filter.matches(entry, today)),
+        };
+        indexes.sort_unstable();
+        indexes.dedup();
+        if indexes.is_empty() {
+            Result::Err("no task matches the selector")
+        } else {
+            Result::Ok(indexes)
+        }
+    }
+
+    fn positions(data: &TodoData, predicate: impl Fn(&TodoEntry) -> bool) -> Vec<usize> {
+        data.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| predicate(entry))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
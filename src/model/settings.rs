@@ -0,0 +1,11 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Settings {
+    pub calendar: Calendar,
+    pub auto_uuid: bool,
+    pub date_on_add: bool,
+    pub colors: Colors,
+    // reference date instead of the system date
+    pub today: Option<DateData>,
+}
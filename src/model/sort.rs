@@ -0,0 +1,101 @@
+use super::*;
+use std::cmp::Ordering;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SortField {
+    Created,
+    Due,
+    Priority,
+    Project,
+    Tag(String),
+    Threshold,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub fn parse(input: &str) -> Result<SortKey, ParsingError> {
+        let (descending, name) = match input.strip_prefix('-') {
+            Option::Some(name) => (true, name),
+            Option::None => (false, input),
+        };
+        let field = match name {
+            "created" => SortField::Created,
+            "due" => SortField::Due,
+            "priority" => SortField::Priority,
+            "project" => SortField::Project,
+            "threshold" | "t" => SortField::Threshold,
+            _ => match name.strip_prefix("tag:") {
+                Option::Some(key) if !key.is_empty() => SortField::Tag(key.into()),
+                _ => {
+                    return Result::Err(ParsingError::new("error parsing sort key"))
+                }
+            },
+        };
+        Result::Ok(SortKey { field, descending })
+    }
+
+    // comma separated list of keys, e.g. due,-priority,project,tag:est
+    pub fn parse_list(input: &str) -> Result<Vec<SortKey>, ParsingError> {
+        input.split(',').map(SortKey::parse).collect()
+    }
+
+    // entries missing the value are sorted last whatever the direction
+    pub fn compare(&self, a: &TodoEntry, b: &TodoEntry) -> Ordering {
+        let ordering = match &self.field {
+            SortField::Created => SortKey::compare_values(&a.created_date, &b.created_date),
+            SortField::Due => SortKey::compare_values(&DateField::Due.value(a), &DateField::Due.value(b)),
+            SortField::Priority => SortKey::compare_values(&a.priority, &b.priority),
+            SortField::Project => SortKey::compare_values(&SortKey::first_project(a), &SortKey::first_project(b)),
+            SortField::Tag(key) => match (a.tag(key), b.tag(key)) {
+                (Option::Some(a), Option::Some(b)) => Option::Some(TodoElement::compare_tag_values(a, b)),
+                _ => Option::None,
+            },
+            SortField::Threshold => {
+                SortKey::compare_values(&DateField::Threshold.value(a), &DateField::Threshold.value(b))
+            }
+        };
+        match (self.descending, ordering) {
+            (true, Option::Some(ordering)) => ordering.reverse(),
+            (false, Option::Some(ordering)) => ordering,
+            (_, Option::None) => SortKey::compare_presence(a, b, &self.field),
+        }
+    }
+
+    pub fn compare_all(keys: &[SortKey], a: &TodoEntry, b: &TodoEntry) -> Ordering {
+        keys.iter()
+            .map(|key| key.compare(a, b))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn compare_values<T: Ord>(a: &Option<T>, b: &Option<T>) -> Option<Ordering> {
+        match (a, b) {
+            (Option::Some(a), Option::Some(b)) => Option::Some(a.cmp(b)),
+            _ => Option::None,
+        }
+    }
+
+    fn compare_presence(a: &TodoEntry, b: &TodoEntry, field: &SortField) -> Ordering {
+        let missing = |entry: &TodoEntry| match field {
+            SortField::Created => entry.created_date.is_none(),
+            SortField::Due => DateField::Due.value(entry).is_none(),
+            SortField::Priority => entry.priority.is_none(),
+            SortField::Project => SortKey::first_project(entry).is_none(),
+            SortField::Tag(key) => entry.tag(key).is_none(),
+            SortField::Threshold => DateField::Threshold.value(entry).is_none(),
+        };
+        missing(a).cmp(&missing(b))
+    }
+
+    fn first_project(entry: &TodoEntry) -> Option<&String> {
+        entry.parts.iter().find_map(|part| match part {
+            TodoElement::Project(project) => Option::Some(project),
+            _ => Option::None,
+        })
+    }
+}
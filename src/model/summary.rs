@@ -0,0 +1,110 @@
+use super::*;
+use std::collections::BTreeMap;
+
+// element aggregated by the projects and contexts commands
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ElementKind {
+    Context,
+    Project,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ElementCount {
+    pub kind: ElementKind,
+    pub name: String,
+    pub open: usize,
+    pub done: usize,
+}
+
+impl ElementKind {
+    pub fn name<'a>(&self, element: &'a TodoElement) -> Option<&'a str> {
+        match (self, element) {
+            (ElementKind::Context, TodoElement::Context(name)) => Option::Some(name),
+            (ElementKind::Project, TodoElement::Project(name)) => Option::Some(name),
+            _ => Option::None,
+        }
+    }
+
+    pub fn element(&self, name: &str) -> TodoElement {
+        match self {
+            ElementKind::Context => TodoElement::context(name),
+            ElementKind::Project => TodoElement::project(name),
+        }
+    }
+
+    pub fn prefix(&self) -> char {
+        match self {
+            ElementKind::Context => '@',
+            ElementKind::Project => '+',
+        }
+    }
+}
+
+impl Model {
+    /// Number of open and done entries using each project or context, sorted by name.
+    /// An entry mentioning the same name twice is counted once.
+    pub fn count_elements(&self, kind: ElementKind, scope: DataScope) -> Vec<ElementCount> {
+        let data: Vec<&TodoData> = match scope {
+            DataScope::Todo => vec![&self.todo_data],
+            DataScope::All => vec![&self.todo_data, &self.done_data],
+        };
+        let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for entry in data.iter().flat_map(|data| data.entries.iter()) {
+            let mut names: Vec<&str> = entry.parts.iter().filter_map(|part| kind.name(part)).collect();
+            names.sort_unstable();
+            names.dedup();
+            for name in names {
+                let (open, done) = counts.entry(name).or_insert((0, 0));
+                match entry.status {
+                    Status::Open => *open += 1,
+                    Status::Done(_) => *done += 1,
+                }
+            }
+        }
+        counts
+            .into_iter()
+            .map(|(name, (open, done))| ElementCount {
+                kind,
+                name: name.into(),
+                open,
+                done,
+            })
+            .collect()
+    }
+}
+
+impl Model {
+    /*
+     * Renames a project or context in both todo and done data. An entry already using the new name
+     * keeps it once, so renaming also merges. Returns every changed entry before and after the
+     * change; with dry_run nothing is modified.
+     */
+    pub fn rename_element(&mut self, kind: ElementKind, old: &str, new: &str, dry_run: bool) -> Vec<(String, String)> {
+        let (old, new) = (kind.element(old), kind.element(new));
+        let mut changes = Vec::new();
+        for entry in self.todo_data.entries.iter_mut().chain(self.done_data.entries.iter_mut()) {
+            if !entry.parts.contains(&old) {
+                continue;
+            }
+            let mut renamed = entry.clone();
+            renamed.parts.clear();
+            for part in &entry.parts {
+                let part = if *part == old { new.clone() } else { part.clone() };
+                if part != new || !renamed.parts.contains(&new) {
+                    renamed.parts.push(part);
+                }
+            }
+            changes.push((format!("{}", entry), format!("{}", renamed)));
+            if !dry_run {
+                *entry = renamed;
+            }
+        }
+        changes
+    }
+}
+
+impl fmt::Display for ElementCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{} ({} open, {} done)", self.kind.prefix(), self.name, self.open, self.done)
+    }
+}
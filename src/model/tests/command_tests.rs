@@ -2,7 +2,13 @@
  * Model operation tests
  */
 
+#[allow(unused_imports)]
+use crate::app::App;
+#[allow(unused_imports)]
+use crate::config::Config;
 use crate::model::*;
+#[allow(unused_imports)]
+use std::path::PathBuf;
 
 #[allow(dead_code)]
 fn create_model_for_testing() -> Model {
@@ -13,18 +19,31 @@ do something else +work",
         )
         .unwrap(),
         done_data: TodoData { entries: vec![] },
+        settings: Settings::default(),
     }
 }
 
+#[allow(dead_code)]
+pub fn create_files_for_testing(name: &str, todo: &str, done: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("tcd-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let todo_path = dir.join("todo.txt");
+    let done_path = dir.join("done.txt");
+    std::fs::write(&todo_path, todo).unwrap();
+    std::fs::write(&done_path, done).unwrap();
+    (todo_path, done_path)
+}
+
 #[test]
 pub fn test_archive() {
     let mut model = create_model_for_testing();
-    match model.execute(Command::Archive(0)) {
+    match model.execute(Command::Archive(Option::Some(0.into()))) {
         Result::Ok(_) => assert_eq!(
             model,
             Model {
                 todo_data: TodoData::parse("do something else +work").unwrap(),
-                done_data: TodoData::parse("do something +home").unwrap()
+                done_data: TodoData::parse("do something +home").unwrap(),
+                settings: Settings::default(),
             },
             "Models are not equal"
         ),
@@ -47,7 +66,8 @@ do something else +work
 do this at @Office"
             )
             .unwrap(),
-            done_data: TodoData { entries: vec![] }
+            done_data: TodoData { entries: vec![] },
+            settings: Settings::default(),
         },
         "Models are not equal"
     );
@@ -56,7 +76,7 @@ do this at @Office"
 #[test]
 pub fn test_do() {
     let mut model = create_model_for_testing();
-    model.execute(Command::Do(0)).expect("test failed");
+    model.execute(Command::Do(0.into())).expect("test failed");
     let Model {
         todo_data: TodoData { entries },
         ..
@@ -67,11 +87,249 @@ pub fn test_do() {
 #[test]
 pub fn test_do_undo() {
     let mut model = create_model_for_testing();
-    model.execute(Command::Do(0)).expect("test failed");
-    model.execute(Command::Undo(0)).expect("test failed");
+    model.execute(Command::Do(0.into())).expect("test failed");
+    model.execute(Command::Undo(0.into())).expect("test failed");
     let Model {
         todo_data: TodoData { entries },
         ..
     } = model;
     assert!(matches!(entries[0].status, Status::Open));
 }
+
+#[test]
+pub fn test_pri_depri() {
+    let mut model = create_model_for_testing();
+    model
+        .execute(Command::Pri(1.into(), Priority { letter: 'B' }))
+        .expect("test failed");
+    assert_eq!(format!("{}", model.todo_data.entries[1]), "(B) do something else +work");
+    model.execute(Command::Depri(1.into())).expect("test failed");
+    assert_eq!(model.todo_data.entries[1].priority, Option::None);
+}
+
+#[test]
+pub fn test_archive_persists_done_file() {
+    let (todo_path, done_path) = create_files_for_testing(
+        "archive",
+        "do something +home\ndo something else +work\n",
+        "x 2020-01-01 old task\n",
+    );
+    let mut app = App::new(Config {
+        todo_filename: Option::Some(todo_path.to_string_lossy().into()),
+        done_filename: Option::Some(done_path.to_string_lossy().into()),
+        ..Config::new()
+    })
+    .unwrap();
+    app.execute(Command::Archive(Option::Some(0.into()))).unwrap();
+    app.save_model().unwrap();
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "do something else +work\n");
+    assert_eq!(
+        std::fs::read_to_string(&done_path).unwrap(),
+        "x 2020-01-01 old task\ndo something +home\n"
+    );
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn test_save_rolls_back_when_done_file_cannot_be_written() {
+    let (todo_path, _) = create_files_for_testing("rollback", "do something +home\n", "");
+    let (_, done_path) = create_files_for_testing("rollback-done", "", "");
+    let mut app = App::new(Config {
+        todo_filename: Option::Some(todo_path.to_string_lossy().into()),
+        done_filename: Option::Some(done_path.to_string_lossy().into()),
+        ..Config::new()
+    })
+    .unwrap();
+    app.execute(Command::Archive(Option::Some(0.into()))).unwrap();
+    // removing the directory of the done file makes its write fail
+    std::fs::remove_dir_all(done_path.parent().unwrap()).unwrap();
+    assert!(app.save_model().is_err());
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "do something +home\n");
+    assert!(!todo_path.with_extension("txt.tmp").exists());
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn test_archive_all_done() {
+    let mut model = Model {
+        todo_data: TodoData::parse(
+            "x 2020-01-02 first
+second
+x 2020-01-03 third
+fourth",
+        )
+        .unwrap(),
+        done_data: TodoData::parse("x 2020-01-01 zeroth").unwrap(),
+        settings: Settings::default(),
+    };
+    model.execute(Command::Archive(Option::None)).expect("test failed");
+    assert_eq!(
+        model,
+        Model {
+            todo_data: TodoData::parse("second\nfourth").unwrap(),
+            done_data: TodoData::parse("x 2020-01-01 zeroth\nx 2020-01-02 first\nx 2020-01-03 third").unwrap(),
+            settings: Settings::default(),
+        }
+    );
+}
+
+#[test]
+pub fn test_add_assigns_uuid_when_enabled() {
+    let mut model = create_model_for_testing();
+    model.settings.auto_uuid = true;
+    model.execute(Command::Add("call mom".into())).expect("test failed");
+    model
+        .execute(Command::Add("call dad uuid:67e55044-10b1-426f-9247-bb680e5fe0c8".into()))
+        .expect("test failed");
+    assert!(model.todo_data.entries[2].uuid().is_some());
+    assert_eq!(
+        format!("{}", model.todo_data.entries[3]),
+        "call dad uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+}
+
+#[test]
+pub fn test_do_recurring_task_keeps_uuids_unique() {
+    let mut model = Model {
+        todo_data: TodoData::parse("water plants rec:1w uuid:67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings::default(),
+    };
+    model.execute(Command::Do(0.into())).expect("test failed");
+    assert_eq!(model.todo_data.entries[1].uuid(), Option::None);
+
+    model.settings.auto_uuid = true;
+    model.execute(Command::Do(1.into())).expect("test failed");
+    let uuids: Vec<&Uuid> = model.todo_data.entries.iter().filter_map(|entry| entry.uuid()).collect();
+    assert_eq!(uuids.len(), 2);
+    assert_ne!(uuids[0], uuids[1]);
+}
+
+#[test]
+pub fn test_lenient_app_reports_problems() {
+    let (todo_path, done_path) = create_files_for_testing("lenient", "ok\nbad due:someday\n", "");
+    let config = || Config {
+        todo_filename: Option::Some(todo_path.to_string_lossy().into()),
+        done_filename: Option::Some(done_path.to_string_lossy().into()),
+        ..Config::new()
+    };
+    assert!(App::new(config()).is_err());
+    let mut app = App::new(Config {
+        lenient: true,
+        ..config()
+    })
+    .unwrap();
+    assert_eq!(app.problems().len(), 1);
+    assert!(app.execute(Command::Check).is_err());
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn test_add_stamps_created_date_when_enabled() {
+    let mut model = create_model_for_testing();
+    model.settings.date_on_add = true;
+    model.settings.today = Option::Some(DateData::parse("2024-03-13").unwrap());
+    for text in &["call mom", "(A) call  dad", "2020-01-01 call grandma"] {
+        model.execute(Command::Add((*text).into())).expect("test failed");
+    }
+    let added: Vec<String> = model.todo_data.entries[2..].iter().map(|entry| format!("{}", entry)).collect();
+    assert_eq!(
+        added,
+        vec!["2024-03-13 call mom", "(A) 2024-03-13 call  dad", "2020-01-01 call grandma"]
+    );
+}
+
+#[test]
+pub fn test_do_moves_priority_to_tag_and_undo_restores_it() {
+    let mut model = Model {
+        todo_data: TodoData::parse("(B) 2024-03-01 call mom").unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings {
+            today: Option::Some(DateData::parse("2024-03-13").unwrap()),
+            ..Settings::default()
+        },
+    };
+    model.execute(Command::Do(0.into())).expect("test failed");
+    assert_eq!(format!("{}", model.todo_data.entries[0]), "x 2024-03-13 2024-03-01 call mom pri:B");
+    model.execute(Command::Undo(0.into())).expect("test failed");
+    assert_eq!(format!("{}", model.todo_data.entries[0]), "(B) 2024-03-01 call mom");
+}
+
+#[test]
+pub fn test_replace_keeps_status_priority_and_created_date() {
+    let mut model = Model {
+        todo_data: TodoData::parse("x 2024-03-10 2024-03-01 call mom\n(A) 2024-03-02 call dad").unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings {
+            today: Option::Some(DateData::parse("2024-03-13").unwrap()),
+            ..Settings::default()
+        },
+    };
+    model.execute(Command::Replace(0.into(), "call grandma".into())).expect("test failed");
+    model.execute(Command::Replace(1.into(), "call dad due:tomorrow".into())).expect("test failed");
+    let lines: Vec<String> = model.todo_data.entries.iter().map(|entry| format!("{}", entry)).collect();
+    assert_eq!(
+        lines,
+        vec!["x 2024-03-10 2024-03-01 call grandma", "(A) 2024-03-02 call dad due:2024-03-14"]
+    );
+}
+
+#[test]
+pub fn test_replace_needs_exactly_one_task() {
+    let mut model = create_model_for_testing();
+    let result = model.execute(Command::Replace(Selector::parse("0,1").unwrap(), "same".into()));
+    assert_eq!(result, Result::Err("the selector must match exactly one task"));
+}
+
+#[test]
+pub fn test_append_and_prepend() {
+    let mut model = create_model_for_testing();
+    model.execute(Command::Append(0.into(), "now @home".into())).expect("test failed");
+    model.execute(Command::Prepend(1.into(), "please".into())).expect("test failed");
+    let lines: Vec<String> = model.todo_data.entries.iter().map(|entry| format!("{}", entry)).collect();
+    assert_eq!(lines, vec!["do something +home now @home", "please do something else +work"]);
+}
+
+#[test]
+pub fn test_edit_runs_editor_on_the_task_line() {
+    let (todo_path, done_path) = create_files_for_testing("edit", "(B) 2024-03-01 call mom\n", "");
+    // the editor copies the edited line over the temporary file
+    let edited_path = todo_path.with_file_name("edited.txt");
+    std::fs::write(&edited_path, "(B) 2024-03-01 call dad\n").unwrap();
+    let mut app = App::new(Config {
+        todo_filename: Option::Some(todo_path.to_string_lossy().into()),
+        done_filename: Option::Some(done_path.to_string_lossy().into()),
+        editor: Option::Some(format!("cp {}", edited_path.to_string_lossy())),
+        ..Config::new()
+    })
+    .unwrap();
+    app.execute(Command::Edit(0.into())).unwrap();
+    app.save_model().unwrap();
+    assert_eq!(std::fs::read_to_string(&todo_path).unwrap(), "(B) 2024-03-01 call dad\n");
+    // a file already waiting at the temporary path is neither followed nor overwritten
+    #[cfg(unix)]
+    {
+        let temp_path = std::env::temp_dir().join(format!("tcd-edit-{}.txt", std::process::id()));
+        let target_path = todo_path.with_file_name("target.txt");
+        std::fs::write(&target_path, "untouched\n").unwrap();
+        std::os::unix::fs::symlink(&target_path, &temp_path).unwrap();
+        let result = app.execute(Command::Edit(0.into()));
+        std::fs::remove_file(&temp_path).unwrap();
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&target_path).unwrap(), "untouched\n");
+    }
+    std::fs::remove_dir_all(todo_path.parent().unwrap()).unwrap();
+}
+
+#[test]
+pub fn test_edit_line_decides_the_status() {
+    let mut model = Model {
+        todo_data: TodoData::parse("x 2024-03-10 2024-03-01 call mom pri:B\ncall dad").unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings::default(),
+    };
+    model.edit(0, "2024-03-01 call mom pri:B").expect("test failed");
+    model.edit(1, "x 2024-03-12 call dad").expect("test failed");
+    let lines: Vec<String> = model.todo_data.entries.iter().map(|entry| format!("{}", entry)).collect();
+    assert_eq!(lines, vec!["(B) 2024-03-01 call mom", "x 2024-03-12 call dad"]);
+}
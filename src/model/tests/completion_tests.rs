@@ -0,0 +1,89 @@
+/*
+ * Completion tests
+ */
+#[allow(unused_imports)]
+use crate::model::*;
+
+#[allow(dead_code)]
+fn create_model_for_testing() -> Model {
+    Model {
+        todo_data: TodoData::parse("call mom +family @phone est:1h\nfix bike +fitness @garage").unwrap(),
+        done_data: TodoData::parse("x 2024-02-01 pay rent +finance @phone estimate:2").unwrap(),
+        settings: Settings::default(),
+    }
+}
+
+#[test]
+pub fn complete_projects_and_contexts_from_both_files() {
+    let model = create_model_for_testing();
+    assert_eq!(model.completions("+fi"), vec!["+finance", "+fitness"]);
+    assert_eq!(model.completions("@"), vec!["@garage", "@phone"]);
+}
+
+#[test]
+pub fn complete_tag_keys_and_dates() {
+    let model = create_model_for_testing();
+    assert_eq!(model.completions("es"), vec!["est:", "estimate:"]);
+    assert_eq!(model.completions("d"), vec!["due:"]);
+    assert_eq!(model.completions("due:t"), vec!["due:today", "due:tomorrow", "due:tuesday", "due:thursday"]);
+    assert_eq!(model.completions("est:"), Vec::<String>::new());
+}
+
+#[allow(dead_code)]
+fn complete(words: &[&str], model: Option<&Model>) -> Vec<String> {
+    let words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+    crate::completions::complete(&words, model)
+}
+
+#[test]
+pub fn complete_commands_and_options() {
+    assert_eq!(complete(&["ar"], Option::None), vec!["archive\tmove tasks to the done file"]);
+    assert_eq!(complete(&["--l"], Option::None), vec!["--lenient\tkeep lines that cannot be parsed as they are"]);
+    assert_eq!(complete(&["-f", "todo.txt", "pr"], Option::None).len(), 3);
+    assert_eq!(complete(&["-f", ""], Option::None), Vec::<String>::new());
+    assert_eq!(complete(&["list", "--s"], Option::None), vec!["--sort"]);
+}
+
+#[test]
+pub fn complete_task_indexes_then_projects() {
+    let model = create_model_for_testing();
+    assert_eq!(
+        complete(&["do", ""], Option::Some(&model)),
+        vec!["0\tcall mom +family @phone est:1h", "1\tfix bike +fitness @garage"]
+    );
+    assert_eq!(complete(&["--lenient", "add", "pay", "+fin"], Option::Some(&model)), vec!["+finance"]);
+}
+
+#[test]
+pub fn every_listed_command_is_parsed() {
+    use crate::args::{parse_command, ErrorType, COMMAND_DEFS};
+    for def in COMMAND_DEFS {
+        let recognized = [vec![def.name], vec![def.name, "1"], vec![def.name, "1", "A"], vec![def.name, "bash"]]
+            .iter()
+            .any(|words| {
+                let words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+                !matches!(parse_command(&words), Result::Err(ErrorType::CannotIdentifyCommand(_)))
+            });
+        assert!(recognized, "{} is not parsed", def.name);
+    }
+}
+
+#[test]
+pub fn scripts_call_the_program() {
+    use crate::args::Shell;
+    for shell in &[Shell::Bash, Shell::Fish, Shell::Zsh] {
+        let script = crate::completions::script(*shell, "tc");
+        assert!(script.contains("tc __complete"));
+        assert!(!script.contains("PROGRAM"));
+    }
+}
+
+#[test]
+pub fn completion_uses_the_files_on_the_command_line() {
+    let words = |words: &[&str]| -> Vec<String> { words.iter().map(|word| word.to_string()).collect() };
+    let typed = crate::completions::typed_config(&words(&["-f", "work.txt", "--done-file", "done.txt", "do", ""])).unwrap();
+    assert_eq!(typed.todo_filename.as_deref(), Option::Some("work.txt"));
+    assert_eq!(typed.done_filename.as_deref(), Option::Some("done.txt"));
+    // while the file name itself is typed the configured files are used
+    assert!(crate::completions::typed_config(&words(&["-f", "work"])).is_none());
+}
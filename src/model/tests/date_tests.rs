@@ -0,0 +1,84 @@
+/*
+ * Date validation, conversion and arithmetic tests
+ */
+#[allow(unused_imports)]
+use crate::model::*;
+#[allow(unused_imports)]
+use chrono::NaiveDate;
+#[allow(unused_imports)]
+use std::convert::TryFrom;
+
+#[test]
+pub fn parse_valid_date() {
+    assert_eq!(
+        DateData::parse("2024-02-29").unwrap(),
+        DateData { year: 2024, month: 2, day: 29 }
+    );
+}
+
+#[test]
+pub fn parse_rejects_dates_not_in_calendar() {
+    for invalid in &["2024-13-45", "2024-02-30", "2023-02-29", "2024-00-10", "2024-04-31"] {
+        assert!(DateData::parse(invalid).is_err(), "{} should not parse", invalid);
+    }
+}
+
+#[test]
+pub fn parse_rejects_malformed_dates() {
+    for invalid in &["2024-01-02-03", "2024-1-02", "24-01-02", "+024-01-02", "2024-01", "2024--01"] {
+        assert!(DateData::parse(invalid).is_err(), "{} should not parse", invalid);
+    }
+}
+
+#[test]
+pub fn invalid_due_date_is_text() {
+    let got: TodoElement = TodoElement::parse("due:2024-02-30");
+    assert!(matches!(got, TodoElement::Text(t) if t == "due:2024-02-30"));
+}
+
+#[test]
+pub fn convert_to_and_from_naive_date() {
+    let naive = NaiveDate::from_ymd(2024, 3, 15);
+    let date = DateData::try_from(naive).unwrap();
+    assert_eq!(date, DateData { year: 2024, month: 3, day: 15 });
+    assert_eq!(NaiveDate::from(&date), naive);
+}
+
+#[test]
+pub fn date_arithmetic() {
+    let date = DateData::parse("2024-12-30").unwrap();
+    assert_eq!(format!("{}", date.add_days(3).unwrap()), "2025-01-02");
+    assert_eq!(format!("{}", date.add_days(-30).unwrap()), "2024-11-30");
+    assert_eq!(format!("{}", date.add_weeks(2).unwrap()), "2025-01-13");
+    assert_eq!(format!("{}", date.add_months(2).unwrap()), "2025-02-28");
+    assert_eq!(format!("{}", date.add_months(-10).unwrap()), "2024-02-29");
+    assert_eq!(format!("{}", date.add_years(1).unwrap()), "2025-12-30");
+    assert_eq!(date.days_until(&date.add_years(1).unwrap()), 365);
+}
+
+#[test]
+pub fn date_arithmetic_stops_at_year_9999() {
+    let date = DateData::parse("2025-10-18").unwrap();
+    assert!(date.add_years(65535).is_err());
+    assert!(date.add_months(-24400).is_err());
+    assert!(date.add_days(3_000_000).is_err());
+    assert!(DateData::parse("9999-12-31").unwrap().add_days(1).is_err());
+    assert_eq!(format!("{}", date.add_years(7974).unwrap()), "9999-10-18");
+    assert!(DateData::try_from(NaiveDate::from_ymd(10000, 1, 1)).is_err());
+}
+
+#[test]
+pub fn commands_reject_dates_after_year_9999() {
+    let mut model = Model {
+        todo_data: TodoData::parse("pay due:9999-12-01 rec:+1m\nwater plants").unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings {
+            today: Option::Some(DateData::parse("2025-10-18").unwrap()),
+            ..Settings::default()
+        },
+    };
+    let before = format!("{}", model.todo_data);
+    assert!(model.execute(crate::args::Command::Add("pay due:+65535y".into())).is_err());
+    assert!(model.execute(crate::args::Command::Do(Selector::parse("0-1").unwrap())).is_err());
+    assert_eq!(format!("{}", model.todo_data), before);
+}
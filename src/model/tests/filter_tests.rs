@@ -0,0 +1,107 @@
+/*
+ * Filter expression tests
+ */
+#[allow(unused_imports)]
+use crate::model::*;
+
+#[allow(dead_code)]
+fn matching(filter: &str, data: &str) -> Vec<String> {
+    let filter = Filter::parse(filter).unwrap();
+    let today = DateData::parse("2024-03-10").unwrap();
+    TodoData::parse(data)
+        .unwrap()
+        .entries
+        .iter()
+        .filter(|entry| filter.matches(entry, &today))
+        .map(|entry| format!("{}", entry))
+        .collect()
+}
+
+#[test]
+pub fn parse_implicit_and_with_negation() {
+    assert_eq!(
+        Filter::parse("+work -@phone").unwrap(),
+        Filter::And(
+            Box::new(Filter::Project("work".into())),
+            Box::new(Filter::Not(Box::new(Filter::Context("phone".into()))))
+        )
+    );
+}
+
+#[test]
+pub fn parse_fails_on_unbalanced_parenthesis() {
+    assert!(Filter::parse("(+work or +home").is_err());
+    assert!(Filter::parse("+work )").is_err());
+    assert!(Filter::parse("+work and").is_err());
+}
+
+#[test]
+pub fn filter_projects_and_contexts() {
+    let data = "call bob +work @phone
+write report +work @office
+buy milk +home";
+    assert_eq!(matching("+work -@phone", data), vec!["write report +work @office"]);
+    assert_eq!(
+        matching("@phone or +home", data),
+        vec!["call bob +work @phone", "buy milk +home"]
+    );
+}
+
+#[test]
+pub fn filter_dates() {
+    let data = "overdue due:2024-03-01
+today due:2024-03-10
+later due:2024-04-01 t:2024-03-20
+no date";
+    assert_eq!(matching("due:<today", data), vec!["overdue due:2024-03-01"]);
+    assert_eq!(
+        matching("due:>=today", data),
+        vec!["today due:2024-03-10", "later due:2024-04-01 t:2024-03-20"]
+    );
+    assert_eq!(matching("t:>=2024-03-15", data), vec!["later due:2024-04-01 t:2024-03-20"]);
+}
+
+#[test]
+pub fn filter_priority_status_and_text() {
+    let data = "(A) urgent thing
+(C) minor Thing
+(D) not important
+x 2024-03-01 finished thing";
+    assert_eq!(matching("pri:A..C", data), vec!["(A) urgent thing", "(C) minor Thing"]);
+    assert_eq!(matching("status:done", data), vec!["x 2024-03-01 finished thing"]);
+    assert_eq!(
+        matching("thing and not (pri:A or status:done)", data),
+        vec!["(C) minor Thing"]
+    );
+}
+
+#[test]
+pub fn filter_tags() {
+    let data = "small pomo:2
+large pomo:12
+none";
+    assert_eq!(matching("pomo:>3", data), vec!["large pomo:12"]);
+    assert_eq!(matching("pomo:2", data), vec!["small pomo:2"]);
+    assert_eq!(matching("-pomo:2", data), vec!["large pomo:12", "none"]);
+}
+
+#[test]
+pub fn fuzzy_text_keeps_exact_projects() {
+    let data = "call mom +family
+write report +work
+wrap gifts +family";
+    let matching_fuzzy = |filter: &str| -> Vec<usize> {
+        let filter = Filter::parse(filter).unwrap().fuzzy();
+        TodoData::parse(data)
+            .unwrap()
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| filter.matches(entry, &DateData::today()))
+            .map(|(i, _)| i)
+            .collect()
+    };
+    assert_eq!(matching_fuzzy("wr"), vec![1, 2]);
+    assert_eq!(matching_fuzzy("wrgf +family"), vec![2]);
+    assert_eq!(matching_fuzzy("+fam"), Vec::<usize>::new());
+}
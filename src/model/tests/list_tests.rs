@@ -0,0 +1,87 @@
+/*
+ * List sorting and visibility tests
+ */
+#[allow(unused_imports)]
+use crate::args::{FutureEntries, ListOptions};
+#[allow(unused_imports)]
+use crate::model::*;
+
+#[allow(dead_code)]
+fn sorted_indexes(sort: &str, data: &str) -> Vec<usize> {
+    let model = Model {
+        todo_data: TodoData::parse(data).unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings::default(),
+    };
+    let options = ListOptions {
+        filter: Option::None,
+        sort: SortKey::parse_list(sort).unwrap(),
+        ..ListOptions::default()
+    };
+    model
+        .list(&options, &DateData::today())
+        .iter()
+        .map(|(i, _)| *i)
+        .collect()
+}
+
+#[test]
+pub fn sort_by_due_puts_missing_last() {
+    let data = "no due
+late due:2024-05-01
+early due:2024-01-01";
+    assert_eq!(sorted_indexes("due", data), vec![2, 1, 0]);
+    assert_eq!(sorted_indexes("-due", data), vec![1, 2, 0]);
+}
+
+#[test]
+pub fn sort_by_several_keys_is_stable() {
+    let data = "(B) first +b
+(A) second +b
+third +a
+(A) fourth +a
+(B) fifth +a";
+    assert_eq!(sorted_indexes("priority,project", data), vec![3, 1, 4, 0, 2]);
+    assert_eq!(sorted_indexes("project,-priority", data), vec![4, 3, 2, 0, 1]);
+    assert_eq!(sorted_indexes("created", data), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+pub fn sort_by_numeric_tag() {
+    let data = "ten est:10
+none
+two est:2";
+    assert_eq!(sorted_indexes("tag:est", data), vec![2, 0, 1]);
+    assert_eq!(sorted_indexes("-tag:est", data), vec![0, 2, 1]);
+}
+
+#[test]
+pub fn parse_unknown_sort_key_fails() {
+    assert!(SortKey::parse_list("due,colour").is_err());
+}
+
+#[test]
+pub fn list_hides_future_threshold_by_default() {
+    let model = Model {
+        todo_data: TodoData::parse(
+            "now
+past t:2024-03-01
+soon t:2024-03-12
+later t:2024-04-01",
+        )
+        .unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings::default(),
+    };
+    let today = DateData::parse("2024-03-10").unwrap();
+    let listed = |future: FutureEntries| -> Vec<usize> {
+        let options = ListOptions {
+            future,
+            ..ListOptions::default()
+        };
+        model.list(&options, &today).iter().map(|(i, _)| *i).collect()
+    };
+    assert_eq!(listed(FutureEntries::Hide), vec![0, 1]);
+    assert_eq!(listed(FutureEntries::IncludeDays(2)), vec![0, 1, 2]);
+    assert_eq!(listed(FutureEntries::Show), vec![0, 1, 2, 3]);
+}
@@ -1,2 +1,11 @@
 pub mod serialization_tests;
-pub mod command_tests;
\ No newline at end of file
+pub mod command_tests;
+pub mod recurrence_tests;
+pub mod filter_tests;
+pub mod list_tests;
+pub mod selector_tests;
+pub mod date_tests;
+pub mod relativedate_tests;
+pub mod summary_tests;
+pub mod render_tests;
+pub mod completion_tests;
@@ -0,0 +1,98 @@
+/*
+ * Recurrence and date arithmetic tests
+ */
+#[allow(unused_imports)]
+use crate::model::*;
+
+#[allow(dead_code)]
+fn date(year: u16, month: u8, day: u8) -> DateData {
+    DateData { year, month, day }
+}
+
+#[test]
+pub fn add_month_clamps_to_month_end() {
+    assert_eq!(date(2024, 1, 31).add_months(1), Result::Ok(date(2024, 2, 29)));
+    assert_eq!(date(2023, 1, 31).add_months(1), Result::Ok(date(2023, 2, 28)));
+    assert_eq!(date(2023, 12, 15).add_months(2), Result::Ok(date(2024, 2, 15)));
+}
+
+#[test]
+pub fn add_year_from_leap_day() {
+    assert_eq!(Calendar::default().add(&date(2024, 2, 29), 1, &RecurrenceTimeUnit::Y), Result::Ok(date(2025, 2, 28)));
+    assert_eq!(date(2024, 2, 29).add_months(48), Result::Ok(date(2028, 2, 29)));
+}
+
+#[test]
+pub fn add_business_days_skips_weekends() {
+    // 2024-01-05 is a friday
+    assert_eq!(Calendar::default().add(&date(2024, 1, 5), 1, &RecurrenceTimeUnit::B), Result::Ok(date(2024, 1, 8)));
+    assert_eq!(Calendar::default().add(&date(2024, 1, 5), 5, &RecurrenceTimeUnit::B), Result::Ok(date(2024, 1, 12)));
+}
+
+#[test]
+pub fn strict_recurrence_moves_from_due_date() {
+    let entry = TodoEntry::parse("2024-01-01 pay rent due:2024-01-31 t:2024-01-25 rec:+1m").unwrap();
+    let next = entry.next_occurrence(&date(2024, 2, 3), &Calendar::default()).unwrap().unwrap();
+    assert_eq!(
+        format!("{}", next),
+        "2024-02-03 pay rent due:2024-02-29 t:2024-02-25 rec:+1m"
+    );
+}
+
+#[test]
+pub fn recurrence_moves_from_today() {
+    let entry = TodoEntry::parse("water plants due:2024-01-10 t:2024-01-08 rec:1w").unwrap();
+    let next = entry.next_occurrence(&date(2024, 1, 12), &Calendar::default()).unwrap().unwrap();
+    assert_eq!(format!("{}", next), "water plants due:2024-01-19 t:2024-01-17 rec:1w");
+}
+
+#[test]
+pub fn do_spawns_next_occurrence() {
+    let mut model = Model {
+        todo_data: TodoData::parse("(A) pay rent due:2024-01-31 rec:+1m\nsomething else").unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings::default(),
+    };
+    model.execute(crate::args::Command::Do(0.into())).expect("test failed");
+    assert!(matches!(model.todo_data.entries[0].status, Status::Done(_)));
+    assert_eq!(model.todo_data.entries.len(), 3);
+    assert_eq!(
+        format!("{}", model.todo_data.entries[2]),
+        "(A) pay rent due:2024-02-29 rec:+1m"
+    );
+}
+
+#[test]
+pub fn entry_without_recurrence_has_no_next_occurrence() {
+    let entry = TodoEntry::parse("pay rent due:2024-01-31").unwrap();
+    assert_eq!(entry.next_occurrence(&date(2024, 2, 3), &Calendar::default()), Result::Ok(Option::None));
+}
+
+#[test]
+pub fn add_business_days_skips_holidays() {
+    let calendar = Calendar::parse(
+        "# public holidays
+2024-01-08 bank holiday
+",
+    )
+    .unwrap();
+    // friday plus five business days skips the weekend and the holiday monday
+    assert_eq!(calendar.add(&date(2024, 1, 5), 5, &RecurrenceTimeUnit::B), Result::Ok(date(2024, 1, 15)));
+    assert!(!calendar.is_business_day(&date(2024, 1, 8)));
+}
+
+#[test]
+pub fn do_uses_holiday_calendar_for_business_days() {
+    let mut model = Model {
+        todo_data: TodoData::parse("send report due:2024-01-05 rec:+1b").unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings {
+            calendar: Calendar {
+                holidays: vec![date(2024, 1, 8)],
+            },
+            ..Settings::default()
+        },
+    };
+    model.execute(crate::args::Command::Do(0.into())).expect("test failed");
+    assert_eq!(format!("{}", model.todo_data.entries[1]), "send report due:2024-01-09 rec:+1b");
+}
@@ -0,0 +1,70 @@
+/*
+ * Relative date tests, 2024-03-13 is a wednesday
+ */
+#[allow(unused_imports)]
+use crate::args::Command;
+#[allow(unused_imports)]
+use crate::model::*;
+
+#[allow(dead_code)]
+fn resolve(input: &str) -> String {
+    let today = DateData::parse("2024-03-13").unwrap();
+    format!("{}", DateData::parse_relative(input, &today, &Calendar::default()).unwrap())
+}
+
+#[test]
+pub fn resolve_named_days() {
+    assert_eq!(resolve("today"), "2024-03-13");
+    assert_eq!(resolve("tomorrow"), "2024-03-14");
+    assert_eq!(resolve("fri"), "2024-03-15");
+    assert_eq!(resolve("Monday"), "2024-03-18");
+    assert_eq!(resolve("wed"), "2024-03-20");
+}
+
+#[test]
+pub fn resolve_offsets() {
+    assert_eq!(resolve("+3d"), "2024-03-16");
+    assert_eq!(resolve("+2w"), "2024-03-27");
+    assert_eq!(resolve("+1m"), "2024-04-13");
+    assert_eq!(resolve("+1y"), "2025-03-13");
+    assert_eq!(resolve("+3b"), "2024-03-18");
+}
+
+#[test]
+pub fn resolve_month_boundaries() {
+    assert_eq!(resolve("eom"), "2024-03-31");
+    assert_eq!(resolve("next-month"), "2024-04-01");
+    assert_eq!(resolve("2024-05-01"), "2024-05-01");
+}
+
+#[test]
+pub fn resolve_end_of_month_before_a_shorter_month() {
+    let eom = |today: &str| {
+        let today = DateData::parse(today).unwrap();
+        format!("{}", DateData::parse_relative("eom", &today, &Calendar::default()).unwrap())
+    };
+    assert_eq!(eom("2024-01-31"), "2024-01-31");
+    assert_eq!(eom("2024-02-29"), "2024-02-29");
+    assert_eq!(eom("2023-02-28"), "2023-02-28");
+    assert_eq!(eom("2024-12-31"), "2024-12-31");
+}
+
+#[test]
+pub fn add_expands_relative_due_and_threshold() {
+    let mut model = Model {
+        todo_data: TodoData { entries: vec![] },
+        done_data: TodoData { entries: vec![] },
+        settings: Settings {
+            today: Option::Some(DateData::parse("2024-03-13").unwrap()),
+            ..Settings::default()
+        },
+    };
+    model
+        .execute(Command::Add("pay  bills due:fri t:+1d note:tomorrow".into()))
+        .expect("test failed");
+    assert_eq!(
+        format!("{}", model.todo_data.entries[0]),
+        "pay  bills due:2024-03-15 t:2024-03-14 note:tomorrow"
+    );
+    assert!(model.execute(Command::Add("call due:someday".into())).is_err());
+}
@@ -0,0 +1,62 @@
+/*
+ * List rendering tests
+ */
+#[allow(unused_imports)]
+use crate::model::*;
+#[allow(unused_imports)]
+use crossterm::style::{Color, Stylize};
+
+#[allow(dead_code)]
+fn render(renderer: &Renderer, line: &str) -> String {
+    let today = DateData::parse("2024-03-13").unwrap();
+    renderer.render(3, &TodoEntry::parse(line).unwrap(), &today)
+}
+
+#[test]
+pub fn plain_output_keeps_entry_as_stored() {
+    let renderer = Renderer::new(Colors::default(), false, Option::None);
+    assert_eq!(render(&renderer, "(A)  call  mom +family"), "[3] (A)  call  mom +family");
+}
+
+#[test]
+pub fn wraps_under_the_entry() {
+    let renderer = Renderer::new(Colors::default(), false, Option::Some(21));
+    assert_eq!(
+        render(&renderer, "(A) call mom about the weekend +family"),
+        "[3] (A) call mom\n    about the weekend\n    +family"
+    );
+}
+
+#[test]
+pub fn colors_priority_project_context_and_overdue() {
+    let renderer = Renderer::new(Colors::default(), true, Option::None);
+    assert_eq!(
+        render(&renderer, "(A) call mom +family @phone due:2024-03-12 due:2024-03-14"),
+        format!(
+            "[3] {} call mom {} {} {} due:2024-03-14",
+            "(A)".with(Color::Yellow),
+            "+family".with(Color::Magenta),
+            "@phone".with(Color::Cyan),
+            "due:2024-03-12".with(Color::Red)
+        )
+    );
+}
+
+#[test]
+pub fn done_entries_use_a_single_color() {
+    let mut colors = Colors::default();
+    colors.set("done", "dark_green").unwrap();
+    let renderer = Renderer::new(colors, true, Option::None);
+    let words = ["x", "2024-03-12", "call", "+family"];
+    let expected: Vec<String> = words.iter().map(|word| format!("{}", word.with(Color::DarkGreen))).collect();
+    assert_eq!(render(&renderer, "x 2024-03-12 call +family"), format!("[3] {}", expected.join(" ")));
+}
+
+#[test]
+pub fn unknown_colors_are_rejected() {
+    let mut colors = Colors::default();
+    assert!(colors.set("priority", "purple").is_err());
+    assert!(colors.set("background", "red").is_err());
+    colors.set("priority", "none").unwrap();
+    assert_eq!(colors.priority, Option::None);
+}
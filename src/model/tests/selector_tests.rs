@@ -0,0 +1,113 @@
+/*
+ * Task selector tests
+ */
+#[allow(unused_imports)]
+use crate::args::Command;
+#[allow(unused_imports)]
+use crate::model::*;
+
+#[allow(dead_code)]
+fn create_model_for_testing() -> Model {
+    Model {
+        todo_data: TodoData::parse(
+            "zero +work
+one +home
+two +work
+three
+four uuid:67e55044-10b1-426f-9247-bb680e5fe0c8",
+        )
+        .unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings::default(),
+    }
+}
+
+#[test]
+pub fn parse_index_lists_and_ranges() {
+    assert_eq!(Selector::parse("3").unwrap(), Selector::Indexes(vec![3]));
+    assert_eq!(Selector::parse("1,4,7").unwrap(), Selector::Indexes(vec![1, 4, 7]));
+    assert_eq!(Selector::parse("2-4,9").unwrap(), Selector::Indexes(vec![2, 3, 4, 9]));
+    assert!(Selector::parse("4-2").is_err());
+    assert!(Selector::parse("1,,2").is_err());
+    assert!(Selector::parse("").is_err());
+}
+
+#[test]
+pub fn parse_filter_and_uuid() {
+    assert_eq!(
+        Selector::parse("+work").unwrap(),
+        Selector::Filter(Filter::Project("work".into()))
+    );
+    assert!(matches!(
+        Selector::parse("uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"),
+        Result::Ok(Selector::Uuid(_))
+    ));
+    assert!(Selector::parse("uuid:67e55044").is_err());
+}
+
+#[test]
+pub fn parse_text_selectors_only_with_a_slash() {
+    assert!(Selector::parse("call").is_err());
+    assert!(Selector::parse("+work and 2").is_err());
+    assert!(Selector::parse("1 2").is_err());
+    assert_eq!(
+        Selector::parse("/call").unwrap(),
+        Selector::Filter(Filter::Text("call".into()))
+    );
+    assert!(Selector::parse("+work due:<today").is_ok());
+}
+
+#[test]
+pub fn resolve_selectors() {
+    let model = create_model_for_testing();
+    let today = DateData::today();
+    let resolve = |input: &str| Selector::parse(input).unwrap().resolve(&model.todo_data, &today);
+    assert_eq!(resolve("2,0,2"), Result::Ok(vec![0, 2]));
+    assert_eq!(resolve("+work"), Result::Ok(vec![0, 2]));
+    assert_eq!(resolve("uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"), Result::Ok(vec![4]));
+    assert!(resolve("+garden").is_err());
+    assert!(resolve("3-5").is_err());
+}
+
+#[test]
+pub fn bulk_commands_apply_to_every_selected_entry() {
+    let mut model = create_model_for_testing();
+    model.execute(Command::Do(Selector::parse("+work").unwrap())).expect("test failed");
+    model.execute(Command::Remove(Selector::parse("1,3").unwrap())).expect("test failed");
+    let statuses: Vec<bool> = model
+        .todo_data
+        .entries
+        .iter()
+        .map(|entry| entry.status == Status::Open)
+        .collect();
+    assert_eq!(statuses, vec![false, false, true]);
+}
+
+#[test]
+pub fn do_and_undo_skip_entries_already_in_that_state() {
+    let create_model = || Model {
+        todo_data: TodoData::parse("x 2020-01-01 old +work\nnew +work pri:B\nx 2020-01-02 done +home pri:A").unwrap(),
+        done_data: TodoData { entries: vec![] },
+        settings: Settings {
+            today: Option::Some(DateData::parse("2024-03-13").unwrap()),
+            ..Settings::default()
+        },
+    };
+    let lines = |model: &Model| -> Vec<String> { model.todo_data.entries.iter().map(|entry| format!("{}", entry)).collect() };
+    let mut model = create_model();
+    model.execute(Command::Do(Selector::parse("+work").unwrap())).expect("test failed");
+    assert_eq!(
+        lines(&model),
+        vec!["x 2020-01-01 old +work", "x 2024-03-13 new +work pri:B", "x 2020-01-02 done +home pri:A"]
+    );
+    let mut model = create_model();
+    model.execute(Command::Undo(Selector::parse("1-2").unwrap())).expect("test failed");
+    assert_eq!(lines(&model), vec!["x 2020-01-01 old +work", "new +work pri:B", "(A) done +home"]);
+}
+
+#[test]
+pub fn bulk_commands_do_nothing_when_an_index_is_out_of_range() {
+    let mut model = create_model_for_testing();
+    assert!(model.execute(Command::Remove(Selector::parse("1,9").unwrap())).is_err());
+    assert_eq!(model, create_model_for_testing());
+}
@@ -13,7 +13,7 @@ pub fn parse_project(){
 #[test]
 pub fn parse_project_fails_when_parsing_a_context(){
     let got: Result<TodoElement, ParsingError> = TodoElement::try_parse_project("@Site1");
-    assert!(matches!(got, Result::Err(ParsingError{message:_})));
+    assert!(matches!(got, Result::Err(ParsingError{message:_, ..})));
 }
 
 #[test]
@@ -89,16 +89,18 @@ pub fn parse_entry(){
         Result::Ok(TodoEntry{
             parts: todo_elements,
             status: Status::Open,
+            priority: Option::None,
             created_date: Option::None,
+            ..
         }) => {
-            for entry in vec!(
+            for entry in [
                 TodoElement::project("Project1"), 
                 TodoElement::context("Site1"),
                 TodoElement::text("Foo bar"),
                 TodoElement::Recurrence{plus:true, count:1, unit:RecurrenceTimeUnit::B},
                 TodoElement::Threshold(DateData{year:2020, month:7, day:26}),
                 TodoElement::Due(DateData{year:2020, month:7, day:20}),
-            ) {
+            ] {
                 if ! todo_elements.contains(&entry){
                     panic!(" entry {:?} not found", entry)
                 }
@@ -122,4 +124,158 @@ pub fn serialization_roundtrip_2(){
     let entry = TodoEntry::parse(original).unwrap();
     let text = format!("{}", entry);
     assert_eq!(text, original);
-}
\ No newline at end of file
+}
+#[test]
+pub fn parse_priority_and_created_date(){
+    match TodoEntry::parse("(A) 2024-01-02 call mom") {
+        Result::Ok(TodoEntry{
+            status: Status::Open,
+            priority: Option::Some(Priority{letter: 'A'}),
+            created_date: Option::Some(DateData{year: 2024, month: 1, day: 2}),
+            parts,
+            ..
+        }) => assert_eq!(parts, vec![TodoElement::text("call mom")]),
+        got => panic!("error while parsing entry {:?}", got)
+    }
+}
+
+#[test]
+pub fn parse_priority_requires_uppercase_letter(){
+    let entry = TodoEntry::parse("(a) call mom").unwrap();
+    assert_eq!(entry.priority, Option::None);
+    assert_eq!(entry.parts, vec![TodoElement::text("(a) call mom")]);
+}
+
+#[test]
+pub fn serialization_roundtrip_priority(){
+    let original = "(B) 2020-05-15 window.requestAnimationFrame +background";
+    let entry = TodoEntry::parse(original).unwrap();
+    let text = format!("{}", entry);
+    assert_eq!(text, original);
+}
+
+#[test]
+pub fn serialization_roundtrip_done(){
+    let original = "x 2020-05-16 2020-05-15 window.requestAnimationFrame +background";
+    let entry = TodoEntry::parse(original).unwrap();
+    let text = format!("{}", entry);
+    assert_eq!(text, original);
+}
+
+#[test]
+pub fn serialization_roundtrip_uuid(){
+    let original = "call mom uuid:67e55044-10b1-426f-9247-bb680e5fe0c8 +family";
+    let entry = TodoEntry::parse(original).unwrap();
+    assert!(entry.uuid().is_some());
+    assert_eq!(format!("{}", entry), original);
+}
+
+#[test]
+pub fn parse_invalid_uuid_falls_back_to_text(){
+    let got: TodoElement = TodoElement::parse("uuid:67e55044");
+    assert!(matches!(got, TodoElement::Text(t) if t == "uuid:67e55044"));
+}
+
+#[test]
+pub fn generated_uuids_are_version_4_and_unique(){
+    let first = format!("{}", Uuid::new_v4());
+    let second = format!("{}", Uuid::new_v4());
+    assert_ne!(first, second);
+    assert_eq!(&first[14..15], "4");
+    assert!("89ab".contains(&first[19..20]));
+    assert_eq!(Uuid::parse(&first).map(|uuid| format!("{}", uuid)).unwrap(), first);
+}
+
+#[test]
+pub fn parse_tag(){
+    let got: TodoElement = TodoElement::parse("est:2h");
+    assert!(matches!(got, TodoElement::Tag{key, value} if key == "est" && value == "2h"));
+}
+
+#[test]
+pub fn parse_url_is_text(){
+    let got: TodoElement = TodoElement::parse("https://example.com");
+    assert!(matches!(got, TodoElement::Text(t) if t == "https://example.com"));
+}
+
+#[test]
+pub fn serialization_roundtrip_tags(){
+    let original = "write spec id:42 h:1 estimate est:2h pomo:3";
+    let entry = TodoEntry::parse(original).unwrap();
+    assert_eq!(entry.parts.len(), 6);
+    assert_eq!(entry.tag("pomo"), Option::Some("3"));
+    assert_eq!(format!("{}", entry), original);
+}
+
+#[test]
+pub fn unchanged_entry_keeps_whitespace(){
+    let original = "(A)  2024-01-02   call  mom   +family ";
+    let entry = TodoEntry::parse(original).unwrap();
+    assert_eq!(format!("{}", entry), original);
+}
+
+#[test]
+pub fn changed_header_keeps_body_whitespace(){
+    let mut entry = TodoEntry::parse("(A)  2024-01-02   call  mom   +family ").unwrap();
+    entry.status = Status::Done(Option::Some(DateData{year: 2024, month: 1, day: 3}));
+    assert_eq!(format!("{}", entry), "x 2024-01-03 (A) 2024-01-02 call  mom   +family ");
+}
+
+#[test]
+pub fn todo_data_roundtrip_is_byte_identical(){
+    for original in &["a  b\r\n\r\nc +p\r\n", "a\n  b\n\nc", ""] {
+        let data = TodoData::parse(original).unwrap();
+        assert_eq!(&format!("{}", data), original);
+    }
+}
+
+#[test]
+pub fn todo_data_only_rewrites_changed_lines(){
+    let mut data = TodoData::parse("first  line\r\nsecond  line\r\nthird  line").unwrap();
+    data.entries[1].parts = vec![TodoElement::text("changed")];
+    data.entries.push(TodoEntry::parse("added").unwrap());
+    assert_eq!(
+        format!("{}", data),
+        "first  line\r\nchanged\r\nthird  line\r\nadded\r\n"
+    );
+}
+
+#[test]
+pub fn parse_lone_completion_mark(){
+    let entry = TodoEntry::parse("x").unwrap();
+    assert_eq!(entry.status, Status::Done(Option::None));
+    assert!(entry.parts.is_empty());
+}
+
+#[test]
+pub fn parse_completion_mark_needs_following_space(){
+    for text in &["xylophone lessons", "X 2020-01-01 shout", " x indented"] {
+        assert_eq!(TodoEntry::parse(text).unwrap().status, Status::Open);
+    }
+}
+
+#[test]
+pub fn parse_keeps_completion_and_creation_dates(){
+    let entry = TodoEntry::parse("x 2020-05-16 2020-05-15 write report").unwrap();
+    assert_eq!(entry.status, Status::Done(Option::Some(DateData{year: 2020, month: 5, day: 16})));
+    assert_eq!(entry.created_date, Option::Some(DateData{year: 2020, month: 5, day: 15}));
+    assert_eq!(entry.parts, vec![TodoElement::text("write report")]);
+}
+
+#[test]
+pub fn parse_reports_line_column_and_token(){
+    let error = TodoData::parse("first\nsecond  due:2020-x-22 +p").unwrap_err();
+    assert_eq!(error.line, Option::Some(2));
+    assert_eq!(error.column, Option::Some(9));
+    assert_eq!(error.token, Option::Some("due:2020-x-22".into()));
+    assert_eq!(format!("{}", error.in_file("todo.txt")), "todo.txt:2:9: error parsing month `due:2020-x-22`");
+}
+
+#[test]
+pub fn parse_lenient_keeps_bad_lines_verbatim(){
+    let original = "first\nsecond  rec:often\nthird t:later\n";
+    let (data, errors) = TodoData::parse_lenient(original);
+    assert_eq!(errors.iter().map(|error| error.line.unwrap()).collect::<Vec<usize>>(), vec![2, 3]);
+    assert_eq!(data.entries.len(), 3);
+    assert_eq!(format!("{}", data), original);
+}
@@ -0,0 +1,77 @@
+/*
+ * Project and context summary tests
+ */
+#[allow(unused_imports)]
+use crate::args::DataScope;
+#[allow(unused_imports)]
+use crate::model::*;
+
+#[allow(dead_code)]
+fn create_model_for_testing() -> Model {
+    Model {
+        todo_data: TodoData::parse(
+            "call mom +family @phone
+x 2024-03-01 fix bike +home +home
+paint fence +home @garden",
+        )
+        .unwrap(),
+        done_data: TodoData::parse("x 2024-02-01 call dad +family @phone").unwrap(),
+        settings: Settings::default(),
+    }
+}
+
+#[allow(dead_code)]
+fn summary(kind: ElementKind, scope: DataScope) -> Vec<String> {
+    create_model_for_testing()
+        .count_elements(kind, scope)
+        .iter()
+        .map(|count| format!("{}", count))
+        .collect()
+}
+
+#[test]
+pub fn projects_are_counted_once_per_entry() {
+    assert_eq!(
+        summary(ElementKind::Project, DataScope::Todo),
+        vec!["+family (1 open, 0 done)", "+home (1 open, 1 done)"]
+    );
+}
+
+#[test]
+pub fn contexts_include_done_file_on_request() {
+    assert_eq!(
+        summary(ElementKind::Context, DataScope::All),
+        vec!["@garden (1 open, 0 done)", "@phone (1 open, 1 done)"]
+    );
+}
+
+#[test]
+pub fn rename_project_in_todo_and_done_data() {
+    let mut model = create_model_for_testing();
+    let changes = model.rename_element(ElementKind::Project, "family", "relatives", false);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(format!("{}", model.todo_data.entries[0]), "call mom +relatives @phone");
+    assert_eq!(format!("{}", model.done_data.entries[0]), "x 2024-02-01 call dad +relatives @phone");
+}
+
+#[test]
+pub fn rename_merges_into_existing_name() {
+    let mut model = create_model_for_testing();
+    model.todo_data.entries[2].parts.push(TodoElement::context("yard"));
+    model.rename_element(ElementKind::Context, "garden", "yard", false);
+    assert_eq!(format!("{}", model.todo_data.entries[2]), "paint fence +home @yard");
+}
+
+#[test]
+pub fn rename_dry_run_changes_nothing() {
+    let mut model = create_model_for_testing();
+    let changes = model.rename_element(ElementKind::Context, "phone", "call", true);
+    assert_eq!(
+        changes,
+        vec![
+            ("call mom +family @phone".to_string(), "call mom +family @call".to_string()),
+            ("x 2024-02-01 call dad +family @phone".to_string(), "x 2024-02-01 call dad +family @call".to_string()),
+        ]
+    );
+    assert_eq!(model, create_model_for_testing());
+}
@@ -10,11 +10,65 @@ impl TodoData {
         let mut result = TodoData {
             entries: Vec::new(),
         };
-        for line in data.lines() {
-            result
-                .entries
-                .push(TodoEntry::parse(line).expect("error parsing"));
+        for (number, line, ending) in TodoData::lines(data) {
+            let entry = TodoEntry::parse(line).map_err(|error| error.at_line(number))?;
+            result.entries.push(TodoData::with_ending(entry, ending));
         }
         Result::Ok(result)
     }
+
+    // lines that cannot be parsed are kept verbatim and reported
+    pub fn parse_lenient(data: &str) -> (TodoData, Vec<ParsingError>) {
+        let mut result = TodoData {
+            entries: Vec::new(),
+        };
+        let mut errors = Vec::new();
+        for (number, line, ending) in TodoData::lines(data) {
+            let entry = match TodoEntry::parse(line) {
+                Result::Ok(entry) => entry,
+                Result::Err(error) => {
+                    errors.push(error.at_line(number));
+                    TodoEntry::parse_lenient(line)
+                }
+            };
+            result.entries.push(TodoData::with_ending(entry, ending));
+        }
+        (result, errors)
+    }
+
+    // (line number, content, line terminator)
+    fn lines(data: &str) -> impl Iterator<Item = (usize, &str, &str)> {
+        data.split_inclusive('\n').enumerate().map(|(i, line)| {
+            let content = line.trim_end_matches(&['\r', '\n'][..]);
+            (i + 1, content, &line[content.len()..])
+        })
+    }
+
+    fn with_ending(mut entry: TodoEntry, ending: &str) -> TodoEntry {
+        if let Option::Some(source) = &mut entry.source {
+            source.ending = Option::Some(ending.into());
+        }
+        entry
+    }
+}
+
+impl fmt::Display for TodoData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // new entries use the line ending of the file, a missing newline at the end of the file is kept
+        let default_ending = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.source.as_ref().and_then(|source| source.ending.as_deref()))
+            .find(|ending| !ending.is_empty())
+            .unwrap_or("\n");
+        for (i, entry) in self.entries.iter().enumerate() {
+            let ending = match entry.source.as_ref().and_then(|source| source.ending.as_deref()) {
+                Option::Some("") if i == self.entries.len() - 1 => "",
+                Option::Some("") | Option::None => default_ending,
+                Option::Some(ending) => ending,
+            };
+            write!(f, "{}{}", entry, ending)?;
+        }
+        Result::Ok(())
+    }
 }
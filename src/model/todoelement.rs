@@ -1,11 +1,48 @@
 use super::*;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::SystemTime;
 
-#[derive(Debug, PartialEq)]
+type ElementParser = Box<dyn Fn(&str) -> Result<TodoElement, ParsingError>>;
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Uuid {
     uuid: u128,
 }
 
-#[derive(Debug, PartialEq)]
+impl Uuid {
+    // random (version 4) uuid, seeded from the standard library's per process random hasher keys
+    pub fn new_v4() -> Uuid {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        let random = || {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(nanos);
+            hasher.finish() as u128
+        };
+        let random = random() << 64 | random();
+        // version 4 in bits 76..80, RFC 4122 variant in bits 62..64
+        Uuid {
+            uuid: (random & !(0xf << 76) | (0x4 << 76)) & !(0x3 << 62) | (0x2 << 62),
+        }
+    }
+
+    // hyphenated form, e.g. 67e55044-10b1-426f-9247-bb680e5fe0c8
+    pub fn parse(uuid_str: &str) -> Result<Uuid, ParsingError> {
+        let groups: Vec<&str> = uuid_str.split('-').collect();
+        let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+        if lengths != [8, 4, 4, 4, 12] || !groups.iter().all(|group| group.chars().all(|c| c.is_ascii_hexdigit())) {
+            return Result::Err(ParsingError::new("error parsing uuid"));
+        }
+        u128::from_str_radix(&groups.concat(), 16)
+            .map(|uuid| Uuid { uuid })
+            .map_err(|_| ParsingError::new("error parsing uuid"))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum RecurrenceTimeUnit {
     B, // business day
     D, // day
@@ -14,6 +51,13 @@ pub enum RecurrenceTimeUnit {
     Y, // year
 }
 
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = format!("{:032x}", self.uuid);
+        write!(f, "{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+    }
+}
+
 impl fmt::Display for RecurrenceTimeUnit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -30,7 +74,7 @@ impl fmt::Display for RecurrenceTimeUnit {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum TodoElement {
     Context(String),
     Due(DateData),
@@ -40,9 +84,12 @@ pub enum TodoElement {
         count: u16,
         unit: RecurrenceTimeUnit,
     },
+    Tag {
+        key: String,
+        value: String,
+    },
     Text(String),
     Threshold(DateData),
-    #[allow(dead_code)]
     Uuid(Uuid),
 }
 
@@ -61,9 +108,10 @@ impl fmt::Display for TodoElement {
                 repr.push_str(&format!("{}", unit));
                 write!(f, "rec:{}", repr)
             }
+            TodoElement::Tag { key, value } => write!(f, "{}:{}", key, value),
             TodoElement::Text(text) => write!(f, "{}", text),
             TodoElement::Threshold(date_data) => write!(f, "t:{}", date_data),
-            TodoElement::Uuid(_) => panic!("not implemented"),
+            TodoElement::Uuid(uuid) => write!(f, "uuid:{}", uuid),
         }
     }
 }
@@ -91,24 +139,18 @@ impl TodoElement {
     }
 
     pub fn is_text(self: &TodoElement) -> bool {
-        if let TodoElement::Text(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, TodoElement::Text(_))
     }
 
     fn create_prefix_parser(
         prefix: char,
         element_constructor: &'static dyn Fn(&str) -> TodoElement,
-    ) -> Box<dyn Fn(&str) -> Result<TodoElement, ParsingError>> {
+    ) -> ElementParser {
         Box::new(move |input: &str| {
             if let Some(data) = input.strip_prefix(prefix) {
                 Result::Ok(element_constructor(data))
             } else {
-                Result::Err(ParsingError {
-                    message: "error parsing entity",
-                })
+                Result::Err(ParsingError::new("error parsing entity"))
             }
         })
     }
@@ -124,14 +166,12 @@ impl TodoElement {
     fn create_date_parser(
         prefix: &'static str,
         constructor: &'static dyn Fn(DateData) -> TodoElement,
-    ) -> Box<dyn Fn(&str) -> Result<TodoElement, ParsingError>> {
+    ) -> ElementParser {
         Box::new(move |input: &str| {
             if let Some(date_str) = input.strip_prefix(prefix) {
                 DateData::parse(date_str).map(constructor)
             } else {
-                Result::Err(ParsingError {
-                    message: "error parsing entity",
-                })
+                Result::Err(ParsingError::new("error parsing entity"))
             }
         })
     }
@@ -152,26 +192,70 @@ impl TodoElement {
                     .trim_start_matches('+')
                     .trim_end_matches(x)
                     .parse::<u16>()
-                    .map_err(|_| ParsingError {
-                        message: "error parsing recurrence",
-                    })?,
+                    .map_err(|_| ParsingError::new("error parsing recurrence"))?,
                 unit: match rec_str.chars().last() {
-                    Some(x) if x == 'd' => Result::Ok(RecurrenceTimeUnit::D),
-                    Some(x) if x == 'b' => Result::Ok(RecurrenceTimeUnit::B),
-                    Some(x) if x == 'm' => Result::Ok(RecurrenceTimeUnit::M),
-                    Some(x) if x == 'w' => Result::Ok(RecurrenceTimeUnit::W),
-                    Some(x) if x == 'y' => Result::Ok(RecurrenceTimeUnit::Y),
-                    _ => Result::Err(ParsingError {
-                        message: "error parsing recurrence",
-                    }),
+                    Some('d') => Result::Ok(RecurrenceTimeUnit::D),
+                    Some('b') => Result::Ok(RecurrenceTimeUnit::B),
+                    Some('m') => Result::Ok(RecurrenceTimeUnit::M),
+                    Some('w') => Result::Ok(RecurrenceTimeUnit::W),
+                    Some('y') => Result::Ok(RecurrenceTimeUnit::Y),
+                    _ => Result::Err(ParsingError::new("error parsing recurrence")),
                 }?,
             })
         } else {
-            Result::Err(ParsingError {
-                message: "error parsing entity",
-            })
+            Result::Err(ParsingError::new("error parsing entity"))
         }
     }
+    fn try_parse_uuid(input: &str) -> Result<TodoElement, ParsingError> {
+        if let Some(uuid_str) = input.strip_prefix("uuid:") {
+            Uuid::parse(uuid_str).map(TodoElement::Uuid)
+        } else {
+            Result::Err(ParsingError::new("error parsing entity"))
+        }
+    }
+
+    // any other key:value token, keys of the known tags are excluded so malformed values stay text
+    fn try_parse_tag(input: &str) -> Result<TodoElement, ParsingError> {
+        match input.split_once(':') {
+            Some((key, value))
+                if !key.is_empty()
+                    && !value.is_empty()
+                    && !value.starts_with("//")
+                    && key.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+                    && !["due", "t", "rec", "uuid"].contains(&key) =>
+            {
+                Result::Ok(TodoElement::Tag {
+                    key: key.into(),
+                    value: value.into(),
+                })
+            }
+            _ => Result::Err(ParsingError::new("error parsing entity")),
+        }
+    }
+
+    // numeric values compare as numbers, anything else as text
+    pub fn compare_tag_values(a: &str, b: &str) -> std::cmp::Ordering {
+        match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal),
+            _ => a.cmp(b),
+        }
+    }
+
+    // like parse, but a token using one of the known prefixes must have a valid value
+    pub fn parse_strict(input: &str) -> Result<TodoElement, ParsingError> {
+        for (prefix, parser) in &[
+            ("due:", TodoElement::try_parse_due as fn(&str) -> Result<TodoElement, ParsingError>),
+            ("t:", TodoElement::try_parse_threshold),
+            ("rec:", TodoElement::try_parse_recurrence),
+            ("uuid:", TodoElement::try_parse_uuid),
+        ] {
+            if input.starts_with(prefix) {
+                return parser(input);
+            }
+        }
+        Result::Ok(TodoElement::parse(input))
+    }
+
     pub fn parse(input: &str) -> TodoElement {
         for parser in &[
             TodoElement::try_parse_project,
@@ -179,10 +263,12 @@ impl TodoElement {
             TodoElement::try_parse_due,
             TodoElement::try_parse_threshold,
             TodoElement::try_parse_recurrence,
+            TodoElement::try_parse_uuid,
+            TodoElement::try_parse_tag,
         ] {
-            match parser(input) {
-                Ok(element) => return element,
-                _ => (), // do nothing with the error, they only exist as a form of documentation and to support unit testing
+            // do nothing with the error, they only exist as a form of documentation and to support unit testing
+            if let Ok(element) = parser(input) {
+                return element;
             }
         }
         TodoElement::text(input)
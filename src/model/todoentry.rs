@@ -1,10 +1,58 @@
 use super::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct TodoEntry {
     pub status: Status,
+    pub priority: Option<Priority>,
     pub created_date: Option<DateData>,
     pub parts: Vec<TodoElement>,
+    pub source: Option<Source>,
+}
+
+/*
+ * Text an entry was parsed from. As long as the entry keeps the parsed content it is written back
+ * exactly as it was read, whitespace included. The content is captured at parse time so writing
+ * does not have to parse the line again.
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub struct Source {
+    pub line: String,
+    // byte offset where the text after status, priority and created date starts
+    pub body_start: usize,
+    // line terminator when read from a file, empty for a last line without newline
+    pub ending: Option<String>,
+    status: Status,
+    priority: Option<Priority>,
+    created_date: Option<DateData>,
+    parts: Vec<TodoElement>,
+}
+
+impl Source {
+    fn new(entry: &TodoEntry, line: &str, body_start: usize) -> Source {
+        Source {
+            line: line.into(),
+            body_start,
+            ending: Option::None,
+            status: entry.status.clone(),
+            priority: entry.priority,
+            created_date: entry.created_date.clone(),
+            parts: entry.parts.clone(),
+        }
+    }
+
+    fn header_unchanged(&self, entry: &TodoEntry) -> bool {
+        self.status == entry.status && self.priority == entry.priority && self.created_date == entry.created_date
+    }
+}
+
+// the source is not part of the content of the entry
+impl PartialEq for TodoEntry {
+    fn eq(&self, other: &TodoEntry) -> bool {
+        self.status == other.status
+            && self.priority == other.priority
+            && self.created_date == other.created_date
+            && self.parts == other.parts
+    }
 }
 
 impl TodoEntry {
@@ -24,50 +72,215 @@ impl TodoEntry {
         parts.push(element);
     }
 
+    pub fn uuid(&self) -> Option<&Uuid> {
+        self.parts.iter().find_map(|part| match part {
+            TodoElement::Uuid(uuid) => Option::Some(uuid),
+            _ => Option::None,
+        })
+    }
+
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.parts.iter().find_map(|part| match part {
+            TodoElement::Tag { key: tag_key, value } if tag_key == key => Option::Some(value.as_str()),
+            _ => Option::None,
+        })
+    }
+
+    /*
+     * Marks the entry done. As done tasks have no priority in todo.txt, an existing priority is kept
+     * as a pri:X tag so that reopening the entry can restore it.
+     */
+    pub fn complete(&mut self, date: DateData) {
+        if let Option::Some(priority) = self.priority.take() {
+            self.parts.push(TodoElement::Tag {
+                key: "pri".into(),
+                value: priority.letter.to_string(),
+            });
+        }
+        self.status = Status::Done(Option::Some(date));
+    }
+
+    pub fn reopen(&mut self) {
+        if self.priority.is_none() {
+            let position = self.parts.iter().position(|part| match part {
+                TodoElement::Tag { key, value } => key == "pri" && Priority::from_letter(value).is_ok(),
+                _ => false,
+            });
+            if let Option::Some(position) = position {
+                if let TodoElement::Tag { value, .. } = self.parts.remove(position) {
+                    self.priority = Priority::from_letter(&value).ok();
+                }
+            }
+        }
+        self.status = Status::Open;
+    }
+
+    /*
+     * Copy of a recurring entry for its next occurrence. With a strict recurrence (rec:+1w) due and
+     * threshold dates move relative to their current values, otherwise the due date moves relative
+     * to today and the threshold keeps its distance to the due date. Fails when a date would move
+     * past the year 9999.
+     */
+    pub fn next_occurrence(&self, today: &DateData, calendar: &Calendar) -> Result<Option<TodoEntry>, ParsingError> {
+        let (plus, count, unit) = match self.parts.iter().find_map(|part| match part {
+            TodoElement::Recurrence { plus, count, unit } => Option::Some((*plus, *count, unit)),
+            _ => Option::None,
+        }) {
+            Option::Some(recurrence) => recurrence,
+            Option::None => return Result::Ok(Option::None),
+        };
+        let due = self.parts.iter().find_map(|part| match part {
+            TodoElement::Due(date) => Option::Some(date),
+            _ => Option::None,
+        });
+        let next_due = match due {
+            Option::Some(due) => Option::Some(calendar.add(if plus { due } else { today }, count, unit)?),
+            Option::None => Option::None,
+        };
+        // the uuid names the completed task, the next occurrence is a different one
+        let parts = self
+            .parts
+            .iter()
+            .filter(|part| !matches!(part, TodoElement::Uuid(_)))
+            .map(|part| match (part, due, &next_due) {
+                (TodoElement::Due(_), _, Option::Some(next_due)) => Result::Ok(TodoElement::Due(next_due.clone())),
+                (TodoElement::Threshold(threshold), _, _) if plus => {
+                    calendar.add(threshold, count, unit).map(TodoElement::Threshold)
+                }
+                (TodoElement::Threshold(threshold), Option::Some(due), Option::Some(next_due)) => {
+                    next_due.add_days(-threshold.days_until(due)).map(TodoElement::Threshold)
+                }
+                (TodoElement::Threshold(_), _, _) => calendar.add(today, count, unit).map(TodoElement::Threshold),
+                (part, _, _) => Result::Ok(part.clone()),
+            })
+            .collect::<Result<Vec<TodoElement>, ParsingError>>()?;
+        Result::Ok(Option::Some(TodoEntry {
+            status: Status::Open,
+            priority: self.priority,
+            created_date: self.created_date.as_ref().map(|_| today.clone()),
+            parts,
+            source: Option::None,
+        }))
+    }
+
+    fn tokenize(data: &str) -> Vec<(usize, &str)> {
+        let mut tokens = Vec::new();
+        let mut start = Option::None;
+        for (i, c) in data.char_indices() {
+            match (c.is_whitespace(), start) {
+                (true, Option::Some(token_start)) => {
+                    tokens.push((token_start, &data[token_start..i]));
+                    start = Option::None;
+                }
+                (false, Option::None) => start = Option::Some(i),
+                _ => (),
+            }
+        }
+        if let Option::Some(token_start) = start {
+            tokens.push((token_start, &data[token_start..]));
+        }
+        tokens
+    }
+
     pub fn parse(data: &str) -> Result<TodoEntry, ParsingError> {
+        TodoEntry::parse_with(data, true)
+    }
+
+    // never fails, tokens with invalid values are kept as text
+    pub fn parse_lenient(data: &str) -> TodoEntry {
+        TodoEntry::parse_with(data, false).unwrap_or_else(|_| {
+            let mut entry = TodoEntry {
+                status: Status::Open,
+                priority: Option::None,
+                created_date: Option::None,
+                parts: vec![TodoElement::text(data)],
+                source: Option::None,
+            };
+            entry.source = Option::Some(Source::new(&entry, data, 0));
+            entry
+        })
+    }
+
+    fn parse_with(data: &str, strict: bool) -> Result<TodoEntry, ParsingError> {
         let mut parts: Vec<TodoElement> = Vec::new();
-        let mut split_parts: Vec<&str> = data.split_whitespace().collect();
-        let mut created_date = Option::None;
+        let tokens = TodoEntry::tokenize(data);
+        let mut split_parts: &[(usize, &str)] = &tokens;
         let mut status = Status::Open;
-        if !split_parts.is_empty() {
-            if split_parts[0].starts_with("x") {
-                status = Status::Done(DateData::parse(split_parts[1]).ok())
+        let mut priority = Option::None;
+        let mut created_date = Option::None;
+        // only a lowercase x followed by a space (or a lone x) at the very start marks a task done
+        if data.starts_with("x ") || data == "x" {
+            status = Status::Done(split_parts.get(1).and_then(|(_, date)| DateData::parse(date).ok()));
+            match status {
+                Status::Done(Option::Some(_)) => split_parts = &split_parts[2..], // skip two
+                _ => split_parts = &split_parts[1..],
             }
-            if let Status::Done(Option::Some(_)) = status {
-                split_parts = split_parts[2..].into(); // skip two
+        }
+        if let Option::Some((_, first)) = split_parts.first() {
+            priority = Priority::parse(first).ok();
+            if priority.is_some() {
+                split_parts = &split_parts[1..];
             }
-            created_date = DateData::parse(split_parts[0]).ok();
-            if let Option::Some(_) = created_date {
-                split_parts = split_parts[1..].into();
+        }
+        if let Option::Some((_, first)) = split_parts.first() {
+            created_date = DateData::parse(first).ok();
+            if created_date.is_some() {
+                split_parts = &split_parts[1..];
             }
         }
-        for split in split_parts.iter() {
-            TodoEntry::push(&mut parts, TodoElement::parse(split));
+        let body_start = split_parts.first().map_or(data.len(), |(start, _)| *start);
+        for (start, split) in split_parts.iter() {
+            let element = if strict {
+                TodoElement::parse_strict(split)
+                    .map_err(|error| error.at_column(data[..*start].chars().count() + 1, split))?
+            } else {
+                TodoElement::parse(split)
+            };
+            TodoEntry::push(&mut parts, element);
         }
-        Result::Ok(TodoEntry {
+        let mut entry = TodoEntry {
             status,
+            priority,
             created_date,
             parts,
-        })
+            source: Option::None,
+        };
+        entry.source = Option::Some(Source::new(&entry, data, body_start));
+        Result::Ok(entry)
+    }
+
+    fn fmt_header(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.status)?;
+        if let Option::Some(priority) = &self.priority {
+            write!(f, "{} ", priority)?;
+        }
+        if let Option::Some(date_data) = &self.created_date {
+            write!(f, "{} ", date_data)?;
+        }
+        Result::Ok(())
     }
 }
 
 impl fmt::Display for TodoEntry {
     fn fmt(self: &TodoEntry, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.status,)
-            .and(if let Option::Some(date_data) = &self.created_date {
-                write!(f, "{} ", date_data)
-            } else {
-                Result::Ok(())
-            })
-            .and(write!(
-                f,
-                "{}",
-                self.parts
-                    .iter()
-                    .map(|p| { format!("{}", p) })
-                    .collect::<Vec<String>>()
-                    .join(" ")
-            ))
+        if let Option::Some(source) = &self.source {
+            if source.parts == self.parts && source.header_unchanged(self) {
+                return write!(f, "{}", source.line);
+            }
+            if source.parts == self.parts {
+                self.fmt_header(f)?;
+                return write!(f, "{}", &source.line[source.body_start..]);
+            }
+        }
+        self.fmt_header(f)?;
+        write!(
+            f,
+            "{}",
+            self.parts
+                .iter()
+                .map(|p| { format!("{}", p) })
+                .collect::<Vec<String>>()
+                .join(" ")
+        )
     }
 }
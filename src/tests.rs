@@ -1,14 +1,18 @@
 #[test]
 pub fn test_parse_config() {
     use crate::*;
-    let mut config = crate::config::Config {
-        todo_filename: Option::None,
-        done_filename: Option::None,
-    };
+    let mut config = crate::config::Config::new();
     read_configuration_from_filecontent(r#"
 todo_filename=/home/avd/todo.txt
 done_filename=/home/avd/done.txt
+holidays_filename=/home/avd/holidays.txt
+auto_uuid=true
+date_on_add=true
+editor=nano -w
+color_project=dark_blue
     "#, &mut config);
     println!("{:?}", &config);
-    assert!(matches!(config, Config { todo_filename: Option::Some(_), ..}))
+    assert_eq!(config.editor.as_deref(), Option::Some("nano -w"));
+    assert_eq!(config.colors.project, Option::Some(crossterm::style::Color::DarkBlue));
+    assert!(matches!(config, Config { todo_filename: Option::Some(_), holidays_filename: Option::Some(_), auto_uuid: true, date_on_add: true, ..}))
 }
\ No newline at end of file